@@ -0,0 +1,327 @@
+use std::hash::Hash;
+
+use anyhow::{ensure, Ok};
+use fxhash::FxHashMap;
+
+use crate::{causal::Dot, Decompose, DotContext, Extract};
+
+/// An add-wins observed-remove set (also known as an AWSet or ORSet), a state-based CRDT that,
+/// unlike the grow-only [`GSet`], supports removals and re-additions.
+///
+/// Each element is tagged with a unique [`Dot`] drawn from the replica's [`DotContext`], so the
+/// same value may be present under several dots. A value is observed in the set as long as at
+/// least one of its dots has not been observed-removed; concurrent add and remove therefore
+/// resolve in favour of the add. Because reconciliation relies on causal context containment, this
+/// type exercises the [`DotContext`] machinery end to end and makes the delta-sync primitives
+/// usable beyond monotone sets.
+///
+/// [`GSet`]: crate::GSet
+///
+/// # Example
+///
+/// ```
+/// use crdt::AWSet;
+///
+/// let mut set = AWSet::new("a");
+/// set.insert("x");
+/// set.insert("y");
+/// set.remove(&"x");
+///
+/// assert!(!set.contains(&"x"));
+/// assert!(set.contains(&"y"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AWSet<I, T> {
+    id: I,
+    ctx: DotContext<I>,
+    elems: FxHashMap<Dot<I>, T>,
+    /// The dots that back an observed removal rather than a live element. Kept separately from
+    /// `elems` so `remove`, `split` and `difference` can still hand out a borrowable `Dot` for a
+    /// tombstone after the value it used to tag has been dropped.
+    tombstones: FxHashMap<Dot<I>, ()>,
+}
+
+/// A view into a slice of an [`AWSet`]'s state. Each entry pairs a [`Dot`] with the value it tags,
+/// or with `None` when the dot denotes an observed removal (a tombstone). Deltas can be joined back
+/// into any [`AWSet`] to synchronize it.
+#[derive(Clone)]
+pub struct Delta<'a, I, T> {
+    set: &'a AWSet<I, T>,
+    pub elems: Vec<(&'a Dot<I>, Option<&'a T>)>,
+}
+
+impl<I, T> AWSet<I, T> {
+    /// Creates an empty `AWSet` owned by the replica identified by `id`.
+    #[inline]
+    #[must_use]
+    pub fn new(id: I) -> Self
+    where
+        I: Default,
+    {
+        Self {
+            id,
+            ctx: DotContext::new(),
+            elems: FxHashMap::default(),
+            tombstones: FxHashMap::default(),
+        }
+    }
+
+    /// Returns `true` if the set observes no elements.
+    pub fn is_empty(&self) -> bool {
+        self.elems.is_empty()
+    }
+
+    /// Returns the number of elements currently observed in the set.
+    pub fn len(&self) -> usize
+    where
+        T: Eq + Hash,
+    {
+        self.elems.values().collect::<std::collections::HashSet<_>>().len()
+    }
+
+    /// An iterator visiting every value currently observed in the set, in arbitrary order.
+    pub fn elements(&self) -> impl Iterator<Item = &T> {
+        self.elems.values()
+    }
+}
+
+impl<I, T> AWSet<I, T>
+where
+    I: Clone + Eq + Hash + Ord,
+    T: Eq + Hash,
+{
+    /// Returns `true` if the set currently observes `value`.
+    pub fn contains(&self, value: &T) -> bool {
+        self.elems.values().any(|v| v == value)
+    }
+
+    /// Adds `value` to the set under a fresh dot and returns a delta carrying it.
+    pub fn insert(&mut self, value: T) -> Delta<'_, I, T>
+    where
+        T: Clone,
+    {
+        let dot = Dot(self.id.clone(), self.ctx.next(&self.id));
+        self.ctx.insert(dot.clone());
+        self.elems.insert(dot.clone(), value);
+
+        let entry = self
+            .elems
+            .get_key_value(&dot)
+            .expect("map must contain the freshly inserted dot");
+        Delta {
+            set: self,
+            elems: vec![(entry.0, Some(entry.1))],
+        }
+    }
+
+    /// Removes every occurrence of `value`, keeping the observed dots as tombstones, and returns a
+    /// delta describing the removals.
+    pub fn remove(&mut self, value: &T) -> Delta<'_, I, T> {
+        let dots = self
+            .elems
+            .iter()
+            .filter_map(|(dot, v)| (v == value).then(|| dot.clone()))
+            .collect::<Vec<_>>();
+
+        for dot in &dots {
+            self.elems.remove(dot);
+            self.tombstones.insert(dot.clone(), ());
+        }
+
+        // The dots stay recorded as tombstones (and in the causal context) so that the removal
+        // wins over any stale re-learning of the same dot from a lagging peer.
+        Delta {
+            set: self,
+            elems: self
+                .tombstones
+                .keys()
+                .filter(|dot| dots.contains(dot))
+                .map(|dot| (dot, None))
+                .collect(),
+        }
+    }
+}
+
+impl<I, T> Decompose<T> for AWSet<I, T>
+where
+    I: Clone + Eq + Hash + Ord,
+    T: Eq + Hash,
+{
+    type Decomposition<'a>
+        = Delta<'a, I, T>
+    where
+        I: 'a,
+        T: 'a;
+
+    fn split(&self) -> Vec<Self::Decomposition<'_>> {
+        let live = self.elems.iter().map(|(dot, v)| Delta {
+            set: self,
+            elems: vec![(dot, Some(v))],
+        });
+
+        let removed = self.tombstones.keys().map(|dot| Delta {
+            set: self,
+            elems: vec![(dot, None)],
+        });
+
+        live.chain(removed).collect()
+    }
+
+    fn join(&mut self, deltas: Vec<Self::Decomposition<'_>>)
+    where
+        T: Clone,
+    {
+        for delta in deltas {
+            for (dot, value) in delta.elems {
+                match value {
+                    // An add wins unless the dot was already observed-removed locally.
+                    Some(v) if !self.observed_removed(dot) => {
+                        self.elems.insert(dot.clone(), v.clone());
+                    }
+                    // A tombstone removes the live element carrying the dot, add-wins aside.
+                    None => {
+                        self.elems.remove(dot);
+                        self.tombstones.insert(dot.clone(), ());
+                    }
+                    _ => {}
+                }
+
+                self.ctx.insert(dot.clone());
+            }
+        }
+    }
+
+    fn difference<'a>(&'a self, remote: &'a Self) -> Self::Decomposition<'a> {
+        // Only the decompositions whose dot the remote has not yet causally observed need to
+        // travel; context containment covers both live elements and tombstones.
+        let live = self
+            .elems
+            .iter()
+            .filter(|(dot, _)| !remote.ctx.contains(dot))
+            .map(|(dot, v)| (dot, Some(v)));
+
+        let removed = self
+            .tombstones
+            .keys()
+            .filter(|dot| !remote.ctx.contains(dot))
+            .map(|dot| (dot, None));
+
+        Delta {
+            set: self,
+            elems: live.chain(removed).collect(),
+        }
+    }
+}
+
+impl<I, T> AWSet<I, T>
+where
+    I: Eq + Hash + Ord,
+{
+    /// Returns `true` if `dot` has been observed by the context but no longer backs a live element.
+    fn observed_removed(&self, dot: &Dot<I>) -> bool {
+        self.ctx.contains(dot) && !self.elems.contains_key(dot)
+    }
+}
+
+impl<I, T> Extract<Dot<I>> for AWSet<I, T>
+where
+    I: Clone + Hash,
+{
+    type Decomposition<'a>
+        = Delta<'a, I, T>
+    where
+        I: 'a,
+        T: 'a;
+
+    fn extract<'a>(delta: &Self::Decomposition<'a>) -> anyhow::Result<Dot<I>> {
+        ensure!(delta.elems.len() == 1);
+        match delta.elems.first() {
+            Some((dot, _)) => Ok((*dot).clone()),
+            None => unreachable!(),
+        }
+    }
+}
+
+impl<I, T> PartialEq for AWSet<I, T>
+where
+    I: Eq + Hash + Ord,
+    T: Eq + Hash,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.elems == other.elems && self.ctx == other.ctx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AWSet, Decompose};
+
+    #[test]
+    fn insertion_and_removal_test() {
+        let mut set = AWSet::new("a");
+        assert!(set.is_empty());
+
+        set.insert("x");
+        set.insert("y");
+        set.insert("z");
+        assert_eq!(set.len(), 3);
+
+        set.remove(&"y");
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&"x"));
+        assert!(!set.contains(&"y"));
+    }
+
+    #[test]
+    fn add_wins_after_concurrent_remove_test() {
+        let mut local = AWSet::new("a");
+        local.insert("x");
+
+        let mut remote = AWSet::new("b");
+        remote.join(local.split());
+
+        // `local` re-adds `x` concurrently with `remote` removing the one it learned.
+        local.insert("x");
+        let readd = local.split();
+        remote.remove(&"x");
+
+        remote.join(readd);
+        assert!(remote.contains(&"x"), "concurrent add should win");
+    }
+
+    #[test]
+    fn remove_delta_joins_into_a_second_replica() {
+        let mut local = AWSet::new("a");
+        local.insert("x");
+
+        let mut remote = AWSet::new("b");
+        remote.join(local.split());
+        assert!(remote.contains(&"x"));
+
+        let removal = local.remove(&"x");
+        assert_eq!(removal.elems.len(), 1, "remove should emit one tombstone");
+
+        remote.join(vec![removal]);
+        assert!(!remote.contains(&"x"), "the tombstone should have reached `remote`");
+    }
+
+    #[test]
+    fn difference_and_join_test() {
+        let mut local = AWSet::new("a");
+        local.insert("x");
+        local.insert("y");
+
+        let mut remote = AWSet::new("b");
+        remote.insert("z");
+
+        let local_unknown = remote.difference(&local);
+        let remote_unknown = local.difference(&remote);
+
+        local.join(vec![local_unknown]);
+        remote.join(vec![remote_unknown]);
+
+        assert!(local.contains(&"z"));
+        assert!(remote.contains(&"x"));
+        assert!(remote.contains(&"y"));
+    }
+}
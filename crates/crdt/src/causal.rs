@@ -3,7 +3,7 @@ use std::{cmp::max, collections::BTreeSet, hash::Hash};
 
 /// A `Dot` is a simple struct that uniquely identifies operations issued by replicas, i.e., it is
 /// a pair (replica id, sequence number).
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Dot<I>(pub I, pub u64);
 
 /// A Dot context is a causality tracking mechanism. It is made of two compoents: a clock and a
@@ -55,6 +55,13 @@ where
         self.clock.get(id).is_some_and(|clock| clock >= seq) || self.cloud.contains(dot)
     }
 
+    /// Records a single `dot` in the context and compacts the representation. This is the
+    /// building block used when merging the causal context carried by a delta-decomposition.
+    pub fn insert(&mut self, dot: Dot<I>) {
+        self.cloud.insert(dot);
+        self.compact();
+    }
+
     /// Compacts the representation of the dot context.
     ///
     /// The algorithm iterates in sorted order thorugh the cloud of dots and determines if each dot
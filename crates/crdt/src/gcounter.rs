@@ -1,9 +1,16 @@
-use std::{borrow::Borrow, cmp::max, hash::Hash};
+use std::{
+    borrow::Borrow,
+    cmp::max,
+    collections::{HashSet, TryReserveError},
+    hash::{BuildHasher, Hash},
+    ops::{BitOr, BitOrAssign},
+};
 
 use anyhow::{ensure, Ok};
-use fxhash::FxHashMap;
+use fxhash::FxBuildHasher;
+use smallvec::{smallvec, SmallVec};
 
-use crate::{Decompose, Extract};
+use crate::{griddle::Griddle, Decompose, Extract};
 
 /// A GCounter is a grow-only counter and a state-based CRDTs. THis data type only supports the
 /// increment and count operations. This is also a named data type meaning that replicas who share
@@ -11,10 +18,17 @@ use crate::{Decompose, Extract};
 ///
 /// # Implementation
 ///
-/// The implementation of a GCounter wraps a [`HashMap`] from the standard library. The replica ids
-/// are the keys and the number of increments represent the keys.
+/// The implementation of a GCounter wraps an incremental-resize hash map ([`Griddle`]). The replica
+/// ids are the keys and the number of increments the values. Because the counter is grow-only, the
+/// map spreads each table resize across many operations instead of paying one synchronous rehash,
+/// keeping the per-entry cost of a large [`Decompose::join`] amortized-constant.
 ///
-/// [`HashMap`]: std::collections::HashMap
+/// Following the standard library's [`HashSet<T, S = RandomState>`] pattern, the counter is
+/// generic over the [`BuildHasher`] `S`, defaulting to [`fxhash`] for local use while letting
+/// security-sensitive deployments opt into a keyed hasher via [`GCounter::with_hasher`].
+///
+/// [`HashSet<T, S = RandomState>`]: std::collections::HashSet
+/// [`fxhash`]: fxhash
 ///
 /// # Example
 ///
@@ -33,8 +47,12 @@ use crate::{Decompose, Extract};
 /// }
 /// ```
 #[derive(Clone, Debug, Default)]
-pub struct GCounter<I> {
-    inner: FxHashMap<I, u64>,
+pub struct GCounter<I, S = FxBuildHasher> {
+    inner: Griddle<I, u64, S>,
+    /// Replica ids that have been permanently pruned via [`GCounter::retain_live`]. `join` and
+    /// `difference` ignore these ids so a lagging peer can never resurrect a retired id's
+    /// contribution.
+    retired: HashSet<I, S>,
 }
 
 /// The `Delta` type represents a view into the state of a given state. They can be joined with any
@@ -62,9 +80,29 @@ pub struct GCounter<I> {
 /// assert_eq!(counter, copy);
 /// ```
 #[derive(Clone)]
-pub struct Delta<'a, I> {
-    counter: &'a GCounter<I>,
-    elems: Vec<(&'a I, &'a u64)>,
+pub struct Delta<'a, I, S = FxBuildHasher> {
+    counter: &'a GCounter<I, S>,
+    elems: SmallVec<[(&'a I, &'a u64); 1]>,
+}
+
+/// An owned counterpart of [`Delta`] that holds a clone of the counter's state rather than
+/// borrowing its source, so it can outlive the counter that produced it. This lets callers buffer
+/// deltas, retain history, or move them across threads.
+#[derive(Clone, Debug)]
+pub struct OwnedDelta<I, S = FxBuildHasher> {
+    inner: Griddle<I, u64, S>,
+}
+
+impl<I, S> OwnedDelta<I, S> {
+    /// Returns the number of entries carried by the delta.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the delta carries no entries.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
 }
 
 impl<I> GCounter<I> {
@@ -75,7 +113,8 @@ impl<I> GCounter<I> {
     /// For performance reasons, this implementations used [`fxhash`] which is faster than the
     /// SipHash 1-3 algorithm used by the standard library. Even though, it does not provide
     /// cryptographic security again DDoS hash attacks, the fact is that this is still a toy
-    /// project.
+    /// project. Deployments that ingest deltas off an untrusted network should instead construct
+    /// the counter with [`GCounter::with_hasher`] and a keyed or SipHash-based [`BuildHasher`].
     ///
     /// [`fxhash`]: fxhash
     ///
@@ -92,14 +131,37 @@ impl<I> GCounter<I> {
     #[must_use]
     pub fn new() -> Self {
         Self {
-            inner: FxHashMap::default(),
+            inner: Griddle::default(),
+            retired: HashSet::default(),
+        }
+    }
+}
+
+impl<I, S> GCounter<I, S> {
+    /// Creates an empty [`GCounter`] that will use `hasher` to hash the replica ids.
+    ///
+    /// This mirrors [`HashSet::with_hasher`] and lets security-sensitive deployments opt into a
+    /// DoS-resistant or keyed hasher while keeping [`fxhash`] as the default for local use.
+    ///
+    /// [`HashSet::with_hasher`]: std::collections::HashSet::with_hasher
+    /// [`fxhash`]: fxhash
+    #[inline]
+    #[must_use]
+    pub fn with_hasher(hasher: S) -> Self
+    where
+        S: Clone,
+    {
+        Self {
+            inner: Griddle::with_hasher(hasher.clone()),
+            retired: HashSet::with_hasher(hasher),
         }
     }
 }
 
-impl<I> GCounter<I>
+impl<I, S> GCounter<I, S>
 where
     I: Eq + Hash,
+    S: BuildHasher,
 {
     /// Returns the count of the counter, i.e., the number of increments.
     ///
@@ -141,17 +203,41 @@ where
     }
 
     /// Transforms the `self` into a `Delta` object that contains its entire state.
-    pub fn as_delta(&self) -> Delta<'_, I> {
+    pub fn as_delta(&self) -> Delta<'_, I, S> {
         Delta {
             counter: self,
             elems: self.inner.iter().collect(),
         }
     }
+
+    /// Returns an owned snapshot of the whole counter, detached from `self` so it can be retained
+    /// or moved independently.
+    #[must_use]
+    pub fn snapshot(&self) -> Self
+    where
+        I: Clone,
+        S: Clone,
+    {
+        self.clone()
+    }
+
+    /// Transforms `self` into an [`OwnedDelta`] holding a clone of its entire state, which — unlike
+    /// [`GCounter::as_delta`] — may outlive the counter that produced it.
+    pub fn as_owned_delta(&self) -> OwnedDelta<I, S>
+    where
+        I: Clone,
+        S: Clone,
+    {
+        OwnedDelta {
+            inner: self.inner.clone(),
+        }
+    }
 }
 
-impl<I> GCounter<I>
+impl<I, S> GCounter<I, S>
 where
     I: Clone + Eq + Hash,
+    S: BuildHasher,
 {
     /// Increments an `id` and returns a [`Delta`] that contains the `id` and its corresponding
     /// counter. If the `id` is not present in the counter, a new entry is initialized with 1.
@@ -168,7 +254,7 @@ where
     /// counter.increment(&"a");
     /// assert_eq!(counter.count_of(&"a"), Some(2));
     /// ```
-    pub fn increment(&mut self, id: &I) -> Delta<'_, I> {
+    pub fn increment(&mut self, id: &I) -> Delta<'_, I, S> {
         match self.inner.get_mut(id) {
             Some(value) => *value += 1,
             None => {
@@ -178,47 +264,194 @@ where
 
         let entry = self
             .inner
-            .get_key_value(id)
+            .iter()
+            .find(|(key, _)| *key == id)
             .expect("map must contain the key `id`");
         Delta {
             counter: self,
-            elems: vec![entry],
+            elems: smallvec![entry],
         }
     }
+
+    /// Fallible counterpart to [`Decompose::join`] that reserves room for the incoming entries up
+    /// front and reports allocation failure via [`TryReserveError`] instead of aborting the
+    /// process.
+    ///
+    /// A replica fed a maliciously large delta batch off the network can otherwise be driven to
+    /// abort on allocation failure with no chance to recover. `try_join` first counts the entries
+    /// that would genuinely advance the local state, [`try_reserve`]s a buffer for exactly that
+    /// many, and only then merges them with element-wise max — mirroring the fallible-allocation
+    /// surface std exposes through [`TryReserveError`]. On failure `self` is left untouched so the
+    /// caller can reject the payload and carry on.
+    ///
+    /// Only that staging buffer is reserved fallibly: inserting the new entries into the backing
+    /// map still grows it through the ordinary allocation path, which aborts on failure.
+    ///
+    /// [`try_reserve`]: Vec::try_reserve
+    pub fn try_join(&mut self, deltas: Vec<Delta<'_, I, S>>) -> Result<(), TryReserveError> {
+        let advances = |entry: &&(&I, &u64)| {
+            let (id, value) = **entry;
+            if self.retired.contains(id) {
+                return false;
+            }
+
+            match self.inner.get(id) {
+                Some(local_value) => value > local_value,
+                None => true,
+            }
+        };
+
+        let count = deltas
+            .iter()
+            .flat_map(|d| d.elems.iter())
+            .filter(advances)
+            .count();
+
+        let mut pending: Vec<(&I, u64)> = Vec::new();
+        pending.try_reserve(count)?;
+        pending.extend(
+            deltas
+                .iter()
+                .flat_map(|d| d.elems.iter())
+                .filter(advances)
+                .map(|(id, value)| (*id, **value)),
+        );
+
+        for (id, remote_value) in pending {
+            match self.inner.get_mut(id) {
+                Some(local_value) => *local_value = max(*local_value, remote_value),
+                None => {
+                    self.inner.insert(id.clone(), remote_value);
+                }
+            }
+        }
+
+        std::result::Result::Ok(())
+    }
+
+    /// Returns the counter's value restricted to the replica ids in `live`, without mutating
+    /// `self`. Lets a caller preview the effect of [`GCounter::retain_live`] before applying it.
+    pub fn filtered_count(&self, live: &HashSet<I, S>) -> u64 {
+        self.inner
+            .iter()
+            .filter(|(id, _)| live.contains(*id))
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// Permanently drops contributions from every replica id not in `live`, reclaiming the space
+    /// their entries held. Pruned ids are recorded in an internal retired set, and `join` and
+    /// `difference` ignore any entry whose id is retired, so a lagging peer's delta can never
+    /// resurrect one.
+    ///
+    /// # Invariant
+    ///
+    /// An id must only leave `live` once every replica still in the system has observed its final
+    /// contribution, e.g. the corresponding node has left the cluster for good and the rest have
+    /// already joined its last delta. Retiring an id some live replica hasn't converged on yet
+    /// permanently discards that replica's pending contribution instead of merely delaying it,
+    /// which breaks convergence rather than preserving it.
+    pub fn retain_live(&mut self, live: &HashSet<I, S>)
+    where
+        S: Default,
+    {
+        let retired = self
+            .inner
+            .iter()
+            .filter(|(id, _)| !live.contains(*id))
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+
+        self.inner = Griddle::from_iter(
+            self.inner
+                .iter()
+                .filter(|(id, _)| live.contains(*id))
+                .map(|(id, count)| (id.clone(), *count)),
+        );
+
+        self.retired.extend(retired);
+    }
 }
 
-impl<I> PartialEq for GCounter<I>
+impl<I, S> PartialEq for GCounter<I, S>
 where
     I: Eq + Hash,
+    S: BuildHasher,
 {
     fn eq(&self, other: &Self) -> bool {
         self.inner == other.inner
     }
 }
 
-impl<I> From<Delta<'_, I>> for GCounter<I>
+impl<I, S> From<Delta<'_, I, S>> for GCounter<I, S>
 where
     I: Clone + Eq + Hash,
+    S: BuildHasher + Default,
 {
-    fn from(value: Delta<'_, I>) -> Self {
+    fn from(value: Delta<'_, I, S>) -> Self {
         Self {
-            inner: FxHashMap::from_iter(value.elems.into_iter().map(|(id, v)| (id.clone(), *v))),
+            inner: Griddle::from_iter(value.elems.into_iter().map(|(id, v)| (id.clone(), *v))),
+            retired: HashSet::default(),
         }
     }
 }
 
-impl<I> Decompose<I> for GCounter<I>
+impl<I, S> From<OwnedDelta<I, S>> for GCounter<I, S>
+where
+    S: Default,
+{
+    fn from(value: OwnedDelta<I, S>) -> Self {
+        Self {
+            inner: value.inner,
+            retired: HashSet::default(),
+        }
+    }
+}
+
+/// Joins `rhs` into `self`, the least-upper-bound merge that takes the element-wise maximum of
+/// every replica's count. This is exactly [`Decompose::join`] spelled as the `|=` operator.
+impl<I, S> BitOrAssign for GCounter<I, S>
+where
+    I: Clone + Eq + Hash,
+    S: BuildHasher,
+{
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.join(rhs.split());
+    }
+}
+
+/// Returns the least-upper-bound merge of two counters, i.e. the element-wise maximum of their
+/// counts, so `a | b` reads as the join of both states.
+impl<I, S> BitOr for GCounter<I, S>
+where
+    I: Clone + Eq + Hash,
+    S: BuildHasher,
+{
+    type Output = GCounter<I, S>;
+
+    fn bitor(mut self, rhs: Self) -> Self::Output {
+        self |= rhs;
+        self
+    }
+}
+
+impl<I, S> Decompose<I> for GCounter<I, S>
 where
     I: Eq + Hash,
+    S: BuildHasher,
 {
-    type Decomposition<'a> = Delta<'a, I> where I: 'a;
+    type Decomposition<'a>
+        = Delta<'a, I, S>
+    where
+        I: 'a,
+        S: 'a;
 
     fn split(&self) -> Vec<Self::Decomposition<'_>> {
         self.inner
             .iter()
             .map(|entry| Delta {
                 counter: self,
-                elems: vec![entry],
+                elems: smallvec![entry],
             })
             .collect()
     }
@@ -230,6 +463,7 @@ where
         deltas
             .into_iter()
             .flat_map(|d| d.elems)
+            .filter(|(id, _)| !self.retired.contains(*id))
             .for_each(|(id, remote_value)| {
                 match self.inner.get_mut(id) {
                     Some(local_value) => *local_value = max(*local_value, *remote_value),
@@ -246,6 +480,7 @@ where
             elems: self
                 .inner
                 .iter()
+                .filter(|(id, _)| !self.retired.contains(*id))
                 .filter(|(id, v)| match remote.inner.get(id) {
                     Some(value) => *v > value,
                     None => true,
@@ -255,11 +490,16 @@ where
     }
 }
 
-impl<'b, I> Extract<(&'b I, &'b u64)> for GCounter<I>
+impl<'b, I, S> Extract<(&'b I, &'b u64)> for GCounter<I, S>
 where
     I: Hash,
 {
-    type Decomposition<'a> = Delta<'b, I> where I: 'a;
+    type Decomposition<'a>
+        = Delta<'b, I, S>
+    where
+        I: 'a,
+        S: 'a,
+        S: 'b;
 
     fn extract(delta: &Self::Decomposition<'b>) -> anyhow::Result<(&'b I, &'b u64)> {
         ensure!(delta.elems.len() == 1);
@@ -272,7 +512,9 @@ where
 
 #[cfg(test)]
 mod tests {
-    use fxhash::FxHashMap;
+    use std::collections::HashSet;
+
+    use crate::griddle::Griddle;
 
     use crate::{Decompose, Extract, GCounter};
 
@@ -316,22 +558,26 @@ mod tests {
     #[test]
     fn difference_test() {
         let mut local = GCounter {
-            inner: FxHashMap::from_iter([("a", 2), ("b", 3), ("c", 1), ("e", 1)]),
+            inner: Griddle::from_iter([("a", 2), ("b", 3), ("c", 1), ("e", 1)]),
+            retired: HashSet::default(),
         };
 
         let mut remote = GCounter {
-            inner: FxHashMap::from_iter([("a", 2), ("b", 1), ("d", 1), ("e", 3)]),
+            inner: Griddle::from_iter([("a", 2), ("b", 1), ("d", 1), ("e", 3)]),
+            retired: HashSet::default(),
         };
 
         let actual_local_diff = GCounter::from(local.difference(&remote));
         let expected_local_diff = GCounter {
-            inner: FxHashMap::from_iter([("b", 3), ("c", 1)]),
+            inner: Griddle::from_iter([("b", 3), ("c", 1)]),
+            retired: HashSet::default(),
         };
         assert_eq!(actual_local_diff, expected_local_diff);
 
         let actual_remote_diff = GCounter::from(remote.difference(&local));
         let expected_remote_diff = GCounter {
-            inner: FxHashMap::from_iter([("d", 1), ("e", 3)]),
+            inner: Griddle::from_iter([("d", 1), ("e", 3)]),
+            retired: HashSet::default(),
         };
         assert_eq!(actual_remote_diff, expected_remote_diff);
 
@@ -354,6 +600,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn try_join_matches_join() {
+        let mut counter = GCounter::new();
+        counter.increment(&"a");
+        counter.increment(&"b");
+        counter.increment(&"a");
+
+        let mut joined = GCounter::new();
+        joined.join(counter.split());
+
+        let mut tried = GCounter::new();
+        tried.try_join(counter.split()).expect("reservation failed");
+
+        assert_eq!(joined, tried);
+        assert_eq!(tried, counter);
+    }
+
+    #[test]
+    fn bitor_matches_join() {
+        let local = GCounter {
+            inner: Griddle::from_iter([("a", 2), ("b", 3), ("c", 1)]),
+            retired: HashSet::default(),
+        };
+
+        let remote = GCounter {
+            inner: Griddle::from_iter([("a", 2), ("b", 1), ("d", 1)]),
+            retired: HashSet::default(),
+        };
+
+        let mut joined = local.clone();
+        joined.join(remote.split());
+
+        let mut merged = local.clone();
+        merged |= remote.clone();
+        assert_eq!(merged, joined);
+
+        assert_eq!(local | remote, joined);
+    }
+
     #[test]
     fn extraction_test() {
         let mut counter = GCounter::new();
@@ -381,4 +666,59 @@ mod tests {
             "extraction is working with large deltas"
         );
     }
+
+    #[test]
+    fn retain_live_prunes_dead_replicas_and_retires_their_ids() {
+        let mut counter = GCounter::new();
+        counter.increment(&"a");
+        counter.increment(&"b");
+        counter.increment(&"b");
+
+        let live = HashSet::from_iter(["b"]);
+        assert_eq!(counter.filtered_count(&live), 2, "filtered_count must sum only the live replicas");
+        assert_eq!(counter.count(), 3, "filtered_count must not mutate the counter");
+
+        counter.retain_live(&live);
+        assert_eq!(counter.count(), 2);
+        assert_eq!(counter.count_of(&"a"), None);
+        assert_eq!(counter.count_of(&"b"), Some(2));
+    }
+
+    #[test]
+    fn retired_ids_are_ignored_by_join_and_difference() {
+        let mut counter = GCounter::new();
+        counter.increment(&"a");
+        counter.increment(&"b");
+        counter.retain_live(&HashSet::from_iter(["b"]));
+
+        // A lagging peer that never learned "a" was retired must not be able to resurrect it.
+        let mut stale_peer = GCounter::new();
+        stale_peer.increment(&"a");
+        counter.join(stale_peer.split());
+        assert_eq!(counter.count_of(&"a"), None);
+
+        let diff = GCounter::from(counter.difference(&stale_peer));
+        assert_eq!(
+            diff.count_of(&"a"),
+            None,
+            "difference must not re-emit a retired id"
+        );
+    }
+
+    #[test]
+    fn retired_ids_are_ignored_by_try_join() {
+        let mut counter = GCounter::new();
+        counter.increment(&"a");
+        counter.increment(&"b");
+        counter.retain_live(&HashSet::from_iter(["b"]));
+
+        // A lagging peer that never learned "a" was retired must not be able to resurrect it
+        // through the fallible reservation path either.
+        let mut stale_peer = GCounter::new();
+        stale_peer.increment(&"a");
+        counter
+            .try_join(stale_peer.split())
+            .expect("reservation failed");
+        assert_eq!(counter.count_of(&"a"), None, "try_join must not re-admit a retired id");
+    }
 }
@@ -0,0 +1,437 @@
+//! An incremental-resize hash map for grow-only CRDT storage.
+//!
+//! [`GCounter`] and [`GSet`] only ever grow, so their backing table only ever expands. With a
+//! single contiguous table a `join` that pushes the table past its load-factor threshold triggers
+//! one synchronous `O(n)` rehash — a latency spike right in the middle of synchronization. This
+//! module adopts [griddle]'s spread-the-resize-load technique: once a resize starts we keep two
+//! tables, a draining `main` and a growing `new`, and every mutating operation migrates only a
+//! small constant number of entries, so no individual `insert`/`join` pays the full rehash. Reads
+//! consult both tables until the migration completes.
+//!
+//! Because the CRDTs never remove entries the table needs no tombstones, which keeps the probing
+//! logic simple: open addressing with linear probing over `Option` slots.
+//!
+//! [`GCounter`]: crate::GCounter
+//! [`GSet`]: crate::GSet
+//! [griddle]: https://docs.rs/griddle
+
+use std::{
+    borrow::Borrow,
+    fmt::{self, Debug},
+    hash::{BuildHasher, Hash},
+};
+
+use fxhash::FxBuildHasher;
+
+/// Capacity of a table the first time it holds anything. Must be a power of two.
+const INITIAL_CAPACITY: usize = 8;
+
+/// Number of entries migrated from `main` into `new` on every mutating operation while a resize is
+/// in flight. A small constant keeps the per-operation cost amortized-constant.
+const MIGRATION_STRIDE: usize = 4;
+
+/// Grows once the live entries reach this fraction (numerator / denominator) of the capacity.
+const LOAD_NUM: usize = 7;
+const LOAD_DEN: usize = 10;
+
+/// A single open-addressing table. Empty slots are `None`; grow-only storage means occupied slots
+/// are never vacated, so no tombstones are required.
+#[derive(Clone)]
+struct Table<K, V> {
+    slots: Box<[Option<(K, V)>]>,
+    len: usize,
+}
+
+impl<K, V> Table<K, V> {
+    /// An empty table that owns no allocation until it first needs one.
+    fn new() -> Self {
+        Self {
+            slots: Box::new([]),
+            len: 0,
+        }
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        let mut slots = Vec::with_capacity(capacity);
+        slots.resize_with(capacity, || None);
+        Self {
+            slots: slots.into_boxed_slice(),
+            len: 0,
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+}
+
+impl<K, V> Table<K, V>
+where
+    K: Eq + Hash,
+{
+    /// Returns the index of the slot holding `key`, or the first empty slot on its probe chain.
+    fn probe<Q, S>(&self, key: &Q, hasher: &S) -> ProbeResult
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+        S: BuildHasher,
+    {
+        debug_assert!(self.capacity().is_power_of_two());
+        let mask = self.capacity() - 1;
+        let mut idx = (hasher.hash_one(key) as usize) & mask;
+        loop {
+            match &self.slots[idx] {
+                Some((k, _)) if k.borrow() == key => return ProbeResult::Found(idx),
+                Some(_) => idx = (idx + 1) & mask,
+                None => return ProbeResult::Empty(idx),
+            }
+        }
+    }
+}
+
+enum ProbeResult {
+    Found(usize),
+    Empty(usize),
+}
+
+/// A hash map whose resizes are spread incrementally across mutating operations.
+///
+/// The API mirrors the subset of [`std::collections::HashMap`] that the CRDTs rely on. It is
+/// generic over the [`BuildHasher`] `S`, defaulting to [`fxhash`] to match the rest of the crate.
+///
+/// [`fxhash`]: fxhash
+pub struct Griddle<K, V, S = FxBuildHasher> {
+    /// The table a resize is draining from; empty when no migration is in flight.
+    main: Table<K, V>,
+    /// The table inserts land in and that `main` migrates into.
+    new: Table<K, V>,
+    /// Index of the next `main` slot to inspect during migration.
+    migrate: usize,
+    hasher: S,
+}
+
+impl<K, V, S> Griddle<K, V, S>
+where
+    S: Default,
+{
+    /// Creates an empty map.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K, V, S> Default for Griddle<K, V, S>
+where
+    S: Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, S> Griddle<K, V, S> {
+    /// Creates an empty map that will use `hasher` to hash the keys.
+    #[must_use]
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            main: Table::new(),
+            new: Table::new(),
+            migrate: 0,
+            hasher,
+        }
+    }
+
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.main.len + self.new.len
+    }
+
+    /// Returns `true` if the map contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` while entries are still being migrated out of `main`.
+    fn migrating(&self) -> bool {
+        self.migrate < self.main.capacity()
+    }
+
+    /// Visits every entry in both tables in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.new
+            .slots
+            .iter()
+            .chain(self.main.slots.iter())
+            .filter_map(|slot| slot.as_ref().map(|(k, v)| (k, v)))
+    }
+
+    /// Visits every value in both tables in arbitrary order.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, v)| v)
+    }
+
+    /// Visits every key in both tables in arbitrary order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(k, _)| k)
+    }
+}
+
+impl<K, V, S> Griddle<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    /// Returns a reference to the value for `key`, consulting both tables.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        if self.new.capacity() > 0 {
+            if let ProbeResult::Found(i) = self.new.probe(key, &self.hasher) {
+                return self.new.slots[i].as_ref().map(|(_, v)| v);
+            }
+        }
+        if self.migrating() {
+            if let ProbeResult::Found(i) = self.main.probe(key, &self.hasher) {
+                return self.main.slots[i].as_ref().map(|(_, v)| v);
+            }
+        }
+        None
+    }
+
+    /// Returns a mutable reference to the value for `key`. A migration step runs first so the hot
+    /// entry has a chance to land in `new`, but the lookup still falls back to `main`.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.migrate_step();
+        if self.new.capacity() > 0 {
+            if let ProbeResult::Found(i) = self.new.probe(key, &self.hasher) {
+                return self.new.slots[i].as_mut().map(|(_, v)| v);
+            }
+        }
+        if self.migrating() {
+            if let ProbeResult::Found(i) = self.main.probe(key, &self.hasher) {
+                return self.main.slots[i].as_mut().map(|(_, v)| v);
+            }
+        }
+        None
+    }
+
+    /// Returns a reference to the stored key equal to `key`, consulting both tables. Useful for
+    /// set-like wrappers that need to borrow the owned key after an insert.
+    pub fn get_key<Q>(&self, key: &Q) -> Option<&K>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        if self.new.capacity() > 0 {
+            if let ProbeResult::Found(i) = self.new.probe(key, &self.hasher) {
+                return self.new.slots[i].as_ref().map(|(k, _)| k);
+            }
+        }
+        if self.migrating() {
+            if let ProbeResult::Found(i) = self.main.probe(key, &self.hasher) {
+                return self.main.slots[i].as_ref().map(|(k, _)| k);
+            }
+        }
+        None
+    }
+
+    /// Returns `true` if the map contains `key`.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Inserts `key` with `value`, returning the previous value if the key was already present.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.migrate_step();
+
+        // Update in place wherever the key already lives so duplicates never cross tables.
+        if self.new.capacity() > 0 {
+            if let ProbeResult::Found(i) = self.new.probe(&key, &self.hasher) {
+                return self.new.slots[i].replace((key, value)).map(|(_, v)| v);
+            }
+        }
+        if self.migrating() {
+            if let ProbeResult::Found(i) = self.main.probe(&key, &self.hasher) {
+                return self.main.slots[i].replace((key, value)).map(|(_, v)| v);
+            }
+        }
+
+        self.maybe_grow();
+        self.raw_insert_new(key, value);
+        None
+    }
+
+    /// Places a brand-new entry into `new`, assuming it has spare capacity.
+    fn raw_insert_new(&mut self, key: K, value: V) {
+        match self.new.probe(&key, &self.hasher) {
+            ProbeResult::Empty(i) => {
+                self.new.slots[i] = Some((key, value));
+                self.new.len += 1;
+            }
+            ProbeResult::Found(_) => unreachable!("key absence is checked before raw insert"),
+        }
+    }
+
+    /// Starts a resize when `new` crosses the load-factor threshold and no migration is running.
+    fn maybe_grow(&mut self) {
+        if self.migrating() {
+            return;
+        }
+
+        let capacity = self.new.capacity();
+        if capacity == 0 {
+            self.new = Table::with_capacity(INITIAL_CAPACITY);
+            return;
+        }
+
+        if (self.new.len + 1) * LOAD_DEN >= capacity * LOAD_NUM {
+            // Promote the full table to `main` and start draining it into a larger `new`.
+            self.main = std::mem::replace(&mut self.new, Table::with_capacity(capacity * 2));
+            self.migrate = 0;
+        }
+    }
+
+    /// Moves up to [`MIGRATION_STRIDE`] live entries from `main` into `new`, then releases `main`
+    /// once it is fully drained.
+    fn migrate_step(&mut self) {
+        if !self.migrating() {
+            return;
+        }
+
+        let mut moved = 0;
+        while moved < MIGRATION_STRIDE && self.migrate < self.main.capacity() {
+            if let Some((key, value)) = self.main.slots[self.migrate].take() {
+                self.main.len -= 1;
+                self.raw_insert_new(key, value);
+                moved += 1;
+            }
+            self.migrate += 1;
+        }
+
+        if self.migrate >= self.main.capacity() {
+            self.main = Table::new();
+            self.migrate = 0;
+        }
+    }
+}
+
+impl<K, V, S> FromIterator<(K, V)> for Griddle<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V, S> Extend<(K, V)> for Griddle<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K, V, S> Clone for Griddle<K, V, S>
+where
+    K: Clone,
+    V: Clone,
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            main: self.main.clone(),
+            new: self.new.clone(),
+            migrate: self.migrate,
+            hasher: self.hasher.clone(),
+        }
+    }
+}
+
+impl<K, V, S> PartialEq for Griddle<K, V, S>
+where
+    K: Eq + Hash,
+    V: PartialEq,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(k, v)| other.get(k).is_some_and(|o| o == v))
+    }
+}
+
+impl<K, V, S> Debug for Griddle<K, V, S>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Griddle;
+
+    #[test]
+    fn insert_and_get_across_resizes() {
+        let mut map: Griddle<u64, u64> = Griddle::new();
+        for i in 0..1_000 {
+            assert!(map.insert(i, i * 2).is_none());
+        }
+
+        assert_eq!(map.len(), 1_000);
+        for i in 0..1_000 {
+            assert_eq!(map.get(&i), Some(&(i * 2)));
+        }
+        assert!(map.get(&1_000).is_none());
+    }
+
+    #[test]
+    fn insert_replaces_existing_value() {
+        let mut map: Griddle<&str, u64> = Griddle::new();
+        assert!(map.insert("a", 1).is_none());
+        assert_eq!(map.insert("a", 2), Some(1));
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.get("a"), Some(&2));
+    }
+
+    #[test]
+    fn get_mut_updates_in_place_during_migration() {
+        let mut map: Griddle<u64, u64> = Griddle::from_iter((0..64).map(|i| (i, i)));
+        for i in 0..64 {
+            *map.get_mut(&i).expect("key should be present") += 100;
+        }
+        for i in 0..64 {
+            assert_eq!(map.get(&i), Some(&(i + 100)));
+        }
+    }
+
+    #[test]
+    fn equality_is_order_independent() {
+        let forward: Griddle<u64, u64> = Griddle::from_iter((0..50).map(|i| (i, i)));
+        let backward: Griddle<u64, u64> = Griddle::from_iter((0..50).rev().map(|i| (i, i)));
+        assert_eq!(forward, backward);
+    }
+}
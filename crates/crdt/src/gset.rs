@@ -1,13 +1,29 @@
-use std::{borrow::Borrow, collections::hash_set::Iter, hash::Hash};
+use std::{
+    borrow::Borrow,
+    collections::TryReserveError,
+    hash::{BuildHasher, Hash},
+    ops::{BitOr, BitOrAssign, Sub},
+};
 
 use anyhow::{ensure, Ok};
-use fxhash::FxHashSet;
+use fxhash::FxBuildHasher;
+use smallvec::{smallvec, SmallVec};
 
-use crate::{Decompose, Extract};
+use crate::{griddle::Griddle, Decompose, Extract};
 
 /// A GSet is a grow-only state and a state-based CRDTs, arguably, the simplest of them all.
 /// As its name suggests, this data type only supports insertion and membership querying.
-/// Its implementation is a wrapper around a [`HashSet`] from the standard library.
+/// Its implementation wraps an incremental-resize hash map ([`Griddle`]) keyed by the set's
+/// values: because the set is grow-only, each table resize is spread across many operations rather
+/// than paid as one synchronous rehash, keeping large [`Decompose::join`]s amortized-constant per
+/// entry.
+///
+/// Following the standard library's [`HashSet<T, S = RandomState>`] pattern, the set is generic
+/// over the [`BuildHasher`] `S`, defaulting to [`fxhash`] for local use while letting
+/// security-sensitive deployments opt into a keyed hasher via [`GSet::with_hasher`].
+///
+/// [`HashSet<T, S = RandomState>`]: std::collections::HashSet
+/// [`fxhash`]: fxhash
 ///
 /// # Example
 ///
@@ -25,8 +41,8 @@ use crate::{Decompose, Extract};
 /// }
 /// ```
 #[derive(Clone, Debug, Default)]
-pub struct GSet<T> {
-    inner: FxHashSet<T>,
+pub struct GSet<T, S = FxBuildHasher> {
+    inner: Griddle<T, (), S>,
 }
 
 /// The `Delta` type represents a view into the state of a given state. They can be joined with any
@@ -35,39 +51,73 @@ pub struct GSet<T> {
 ///
 /// [`From`]: std::convert::From
 #[derive(Clone)]
-pub struct Delta<'a, T> {
-    set: &'a GSet<T>,
-    pub elems: Vec<&'a T>,
+pub struct Delta<'a, T, S = FxBuildHasher> {
+    set: &'a GSet<T, S>,
+    /// The decomposition's elements, stored inline for the common singleton case to avoid a heap
+    /// allocation on the `split`/`insert` hot path, spilling to the heap only for larger deltas.
+    pub elems: SmallVec<[&'a T; 1]>,
+}
+
+/// An owned join-decomposition that, unlike [`Delta`], holds a clone of the set's state rather than
+/// borrowing its source. Because it owns its state it can outlive the replica
+/// that produced it, letting callers buffer deltas, retain history, or move them across threads.
+#[derive(Clone, Debug)]
+pub struct OwnedDelta<T, S = FxBuildHasher> {
+    inner: Griddle<T, (), S>,
+}
+
+impl<T, S> OwnedDelta<T, S> {
+    /// Returns the number of elements carried by the delta.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the delta carries no elements.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
 }
 
 impl<T> GSet<T> {
     /// Creates an empty `Gset`.
-    /// Essentially, this CRDT is a wrapper for an [`HashSet`] from the standard library.
-    ///
-    /// [`HashSet`]: std::collections::HashSet
     ///
     /// # Performance
     ///
     /// For performance reasons, this implementations used [`fxhash`] which is faster than the
     /// SipHash 1-3 algorithm used by the standard library. Even though, it does not provide
     /// cryptographic security again DDoS hash attacks, the fact is that this is still a toy
-    /// project.
+    /// project. Deployments that ingest deltas off an untrusted network should instead construct
+    /// the set with [`GSet::with_hasher`] and a keyed or SipHash-based [`BuildHasher`].
     ///
     /// [`fxhash`]: fxhash
     #[inline]
     #[must_use]
     pub fn new() -> Self {
         Self {
-            inner: FxHashSet::default(),
+            inner: Griddle::default(),
         }
     }
 }
 
-impl<T> GSet<T> {
-    /// An iterator visiting all the elements in arbitrary order.
-    /// Since this is a wrapper around [`HashSet`] the iterator returned is the internal set iterator.
+impl<T, S> GSet<T, S> {
+    /// Creates an empty [`GSet`] that will use `hasher` to hash the values.
+    ///
+    /// This mirrors [`HashSet::with_hasher`] and lets security-sensitive deployments opt into a
+    /// DoS-resistant or keyed hasher while keeping [`fxhash`] as the default for local use.
     ///
-    /// [`HashSet`]: std::collections::HashSet
+    /// [`HashSet::with_hasher`]: std::collections::HashSet::with_hasher
+    /// [`fxhash`]: fxhash
+    #[inline]
+    #[must_use]
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            inner: Griddle::with_hasher(hasher),
+        }
+    }
+}
+
+impl<T, S> GSet<T, S> {
+    /// An iterator visiting all the elements in arbitrary order.
     ///
     /// # Examples
     /// ```
@@ -81,8 +131,8 @@ impl<T> GSet<T> {
     ///     println!("{x}");
     /// }
     /// ```
-    pub fn iter(&self) -> Iter<'_, T> {
-        self.inner.iter()
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.inner.keys()
     }
 
     /// Returns `true` if the set contains no elements.
@@ -96,42 +146,66 @@ impl<T> GSet<T> {
     }
 
     /// Transforms the `self` into a `Delta` object that contains its entire state.
-    pub fn as_delta(&self) -> Delta<'_, T> {
+    pub fn as_delta(&self) -> Delta<'_, T, S> {
         Delta {
             set: self,
-            elems: self.inner.iter().collect(),
+            elems: self.inner.keys().collect(),
+        }
+    }
+
+    /// Returns an owned snapshot of the whole set, detached from `self` so it can be retained or
+    /// moved independently.
+    #[must_use]
+    pub fn snapshot(&self) -> Self
+    where
+        T: Clone,
+        S: Clone,
+    {
+        self.clone()
+    }
+
+    /// Transforms `self` into an [`OwnedDelta`] holding a clone of its entire state, which — unlike
+    /// [`GSet::as_delta`] — may outlive the set that produced it.
+    pub fn as_owned_delta(&self) -> OwnedDelta<T, S>
+    where
+        T: Clone,
+        S: Clone,
+    {
+        OwnedDelta {
+            inner: self.inner.clone(),
         }
     }
 }
 
-impl<T> GSet<T>
+impl<T, S> GSet<T, S>
 where
     T: Eq + Hash,
+    S: BuildHasher,
 {
     /// Returns `true` if the set contains a value.
     pub fn contains<Q: ?Sized + Hash + Eq>(&self, value: &Q) -> bool
     where
         T: Borrow<Q>,
     {
-        self.inner.contains(value)
+        self.inner.contains_key(value)
     }
 
     /// Returns `true` if `self` and `other` are disjoint, i.e., `self` does not contain any
     /// values from `other` and vice-versa.
-    pub fn is_disjoint(&self, other: &GSet<T>) -> bool {
-        self.inner.is_disjoint(&other.inner)
+    pub fn is_disjoint(&self, other: &GSet<T, S>) -> bool {
+        self.iter().all(|v| !other.contains(v))
     }
 
     /// Returns `true` if `self` is a subset of `other`, i.e., all the values of `self` are
     /// contained in `other`.
-    pub fn is_subset(&self, other: &GSet<T>) -> bool {
-        self.inner.is_subset(&other.inner)
+    pub fn is_subset(&self, other: &GSet<T, S>) -> bool {
+        self.len() <= other.len() && self.iter().all(|v| other.contains(v))
     }
 
     /// Returns `true` if `self` is a superset of `other`, i.e., `self` contains at least all the
     /// values of `other`.
-    pub fn is_superset(&self, other: &GSet<T>) -> bool {
-        self.inner.is_superset(&other.inner)
+    pub fn is_superset(&self, other: &GSet<T, S>) -> bool {
+        other.is_subset(self)
     }
 
     /// Adds a value to the set.
@@ -149,57 +223,149 @@ where
     /// assert!(set.insert("b").is_none());
     /// assert_eq!(set.len(), 2);
     /// ```
-    pub fn insert(&mut self, value: T) -> Option<Delta<'_, T>>
+    pub fn insert(&mut self, value: T) -> Option<Delta<'_, T, S>>
     where
         T: Clone,
     {
         // FIXME: Change this when `get_or_insert` becomes stable. This way it would be possile to
-        // remove the need for cloning value. A workaround would be to change the implementation to
-        // use a HashMap, but it feels cumbersome and makes everything more complex.
+        // remove the need for cloning value.
         // See more: https://github.com/rust-lang/rust/pull/60894
-        if self.inner.contains(&value) {
+        if self.inner.contains_key(&value) {
             return None;
         }
 
-        self.inner.insert(value.clone());
-        self.inner.get(&value).map(|v| Delta {
+        self.inner.insert(value.clone(), ());
+        self.inner.get_key(&value).map(|v| Delta {
             set: self,
-            elems: vec![v],
+            elems: smallvec![v],
         })
     }
+
+    /// Fallible counterpart to [`Decompose::join`] that reserves room for the incoming values up
+    /// front and reports allocation failure via [`TryReserveError`] instead of aborting the
+    /// process.
+    ///
+    /// A replica fed a maliciously large delta batch off the network can otherwise be driven to
+    /// abort on allocation failure with no chance to recover. `try_join` first counts the values
+    /// not already present, [`try_reserve`]s a buffer for exactly that many, and only then unions
+    /// them in — mirroring the fallible-allocation surface std exposes through [`TryReserveError`].
+    /// On failure `self` is left untouched so the caller can reject the payload and carry on.
+    ///
+    /// Only that staging buffer is reserved fallibly: inserting the new values into the backing
+    /// map still grows it through the ordinary allocation path, which aborts on failure.
+    ///
+    /// [`try_reserve`]: Vec::try_reserve
+    pub fn try_join(&mut self, deltas: Vec<Delta<'_, T, S>>) -> Result<(), TryReserveError>
+    where
+        T: Clone,
+    {
+        let count = deltas
+            .iter()
+            .flat_map(|d| d.elems.iter())
+            .filter(|v| !self.inner.contains_key(**v))
+            .count();
+
+        let mut unknown_elements: Vec<(T, ())> = Vec::new();
+        unknown_elements.try_reserve(count)?;
+        unknown_elements.extend(
+            deltas
+                .iter()
+                .flat_map(|d| d.elems.iter())
+                .filter(|v| !self.inner.contains_key(**v))
+                .map(|v| ((*v).clone(), ())),
+        );
+
+        self.inner.extend(unknown_elements);
+        std::result::Result::Ok(())
+    }
 }
 
-impl<T> PartialEq for GSet<T>
+impl<T, S> PartialEq for GSet<T, S>
 where
     T: Eq + Hash,
+    S: BuildHasher,
 {
     fn eq(&self, other: &Self) -> bool {
         self.inner == other.inner
     }
 }
 
-impl<T> From<Delta<'_, T>> for GSet<T>
+impl<T, S> From<Delta<'_, T, S>> for GSet<T, S>
 where
     T: Clone + Eq + Hash,
+    S: BuildHasher + Default,
 {
-    fn from(value: Delta<'_, T>) -> Self {
+    fn from(value: Delta<'_, T, S>) -> Self {
         Self {
-            inner: FxHashSet::from_iter(value.elems.into_iter().cloned()),
+            inner: Griddle::from_iter(value.elems.into_iter().map(|v| (v.clone(), ()))),
         }
     }
 }
 
-impl<T> Decompose<T> for GSet<T>
+impl<T, S> From<OwnedDelta<T, S>> for GSet<T, S> {
+    fn from(value: OwnedDelta<T, S>) -> Self {
+        Self { inner: value.inner }
+    }
+}
+
+/// Joins `rhs` into `self`, the least-upper-bound merge that takes the union of both sets. This is
+/// exactly [`Decompose::join`] spelled as the `|=` operator.
+impl<T, S> BitOrAssign for GSet<T, S>
+where
+    T: Clone + Eq + Hash,
+    S: BuildHasher,
+{
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.join(rhs.split());
+    }
+}
+
+/// Returns the least-upper-bound merge of two sets, i.e. their union, so `a | b` reads as the join
+/// of both states.
+impl<T, S> BitOr for GSet<T, S>
+where
+    T: Clone + Eq + Hash,
+    S: BuildHasher,
+{
+    type Output = GSet<T, S>;
+
+    fn bitor(mut self, rhs: Self) -> Self::Output {
+        self |= rhs;
+        self
+    }
+}
+
+/// Returns what `self` has that `rhs` lacks, i.e. a convenience wrapper over
+/// [`Decompose::difference`] that materializes the resulting delta into a concrete [`GSet`] via
+/// `a - b`.
+impl<T, S> Sub for GSet<T, S>
+where
+    T: Clone + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    type Output = GSet<T, S>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        GSet::from(self.difference(&rhs))
+    }
+}
+
+impl<T, S> Decompose<T> for GSet<T, S>
 where
     T: Eq + Hash,
+    S: BuildHasher,
 {
-    type Decomposition<'a> = Delta<'a, T> where T: 'a;
+    type Decomposition<'a>
+        = Delta<'a, T, S>
+    where
+        T: 'a,
+        S: 'a;
 
     fn split(&self) -> Vec<Self::Decomposition<'_>> {
         self.iter()
             .map(|v| Delta {
                 set: self,
-                elems: vec![v],
+                elems: smallvec![v],
             })
             .collect()
     }
@@ -211,8 +377,8 @@ where
         let unknown_elements = deltas
             .into_iter()
             .flat_map(|d| d.elems)
-            .filter(|v| !self.inner.contains(v))
-            .cloned()
+            .filter(|v| !self.inner.contains_key(v))
+            .map(|v| (v.clone(), ()))
             .collect::<Vec<_>>();
 
         self.inner.extend(unknown_elements);
@@ -221,16 +387,24 @@ where
     fn difference<'a>(&'a self, remote: &'a Self) -> Self::Decomposition<'a> {
         Delta {
             set: self,
-            elems: self.inner.difference(&remote.inner).collect(),
+            elems: self
+                .inner
+                .keys()
+                .filter(|v| !remote.inner.contains_key(*v))
+                .collect(),
         }
     }
 }
 
-impl<T> Extract<T> for GSet<T>
+impl<T, S> Extract<T> for GSet<T, S>
 where
     T: Hash,
 {
-    type Decomposition<'a> = Delta<'a, T> where T: 'a;
+    type Decomposition<'a>
+        = Delta<'a, T, S>
+    where
+        T: 'a,
+        S: 'a;
 
     fn extract<'a>(delta: &Self::Decomposition<'a>) -> anyhow::Result<&'a T> {
         ensure!(delta.elems.len() == 1);
@@ -243,7 +417,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use fxhash::FxHashSet;
+    use crate::griddle::Griddle;
 
     use crate::{gset::GSet, Decompose, Extract};
 
@@ -288,25 +462,79 @@ mod tests {
         assert_eq!(set, remote);
     }
 
+    #[test]
+    fn try_join_matches_join() {
+        let mut set = GSet::new();
+        set.insert("a");
+        set.insert("b");
+        set.insert("c");
+
+        let mut joined = GSet::new();
+        joined.join(set.split());
+
+        let mut tried = GSet::new();
+        tried.try_join(set.split()).expect("reservation failed");
+
+        assert_eq!(joined, tried);
+        assert_eq!(tried, set);
+    }
+
+    #[test]
+    fn bitor_matches_join() {
+        let local = GSet {
+            inner: Griddle::from_iter(["a", "b", "c"].map(|v| (v, ()))),
+        };
+
+        let remote = GSet {
+            inner: Griddle::from_iter(["a", "d"].map(|v| (v, ()))),
+        };
+
+        let mut joined = local.clone();
+        joined.join(remote.split());
+
+        let mut merged = local.clone();
+        merged |= remote.clone();
+        assert_eq!(merged, joined);
+
+        assert_eq!(local | remote, joined);
+    }
+
+    #[test]
+    fn sub_matches_difference() {
+        let local = GSet {
+            inner: Griddle::from_iter(["a", "b", "c", "e"].map(|v| (v, ()))),
+        };
+
+        let remote = GSet {
+            inner: Griddle::from_iter(["a", "b", "d", "f"].map(|v| (v, ()))),
+        };
+
+        let expected_local_diff = GSet::from(local.difference(&remote));
+        let expected_remote_diff = GSet::from(remote.difference(&local));
+
+        assert_eq!(local.clone() - remote.clone(), expected_local_diff);
+        assert_eq!(remote - local, expected_remote_diff);
+    }
+
     #[test]
     fn difference_test() {
         let mut local = GSet {
-            inner: FxHashSet::from_iter(["a", "b", "c", "e"]),
+            inner: Griddle::from_iter(["a", "b", "c", "e"].map(|v| (v, ()))),
         };
 
         let mut remote = GSet {
-            inner: FxHashSet::from_iter(["a", "b", "d", "f"]),
+            inner: Griddle::from_iter(["a", "b", "d", "f"].map(|v| (v, ()))),
         };
 
         let actual_local_diff = GSet::from(local.difference(&remote));
         let expected_local_diff = GSet {
-            inner: FxHashSet::from_iter(["c", "e"]),
+            inner: Griddle::from_iter(["c", "e"].map(|v| (v, ()))),
         };
         assert_eq!(actual_local_diff, expected_local_diff);
 
         let actual_remote_diff = GSet::from(remote.difference(&local));
         let expected_remote_diff = GSet {
-            inner: FxHashSet::from_iter(["d", "f"]),
+            inner: Griddle::from_iter(["d", "f"].map(|v| (v, ()))),
         };
         assert_eq!(actual_remote_diff, expected_remote_diff);
 
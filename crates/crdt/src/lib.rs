@@ -1,11 +1,16 @@
 #![allow(dead_code)]
 
+mod awset;
 mod causal;
 mod counter;
+mod griddle;
+mod pncounter;
 mod set;
 
+pub use crate::awset::AWSet;
 pub use crate::causal::{Dot, DotContext};
 pub use crate::counter::GCounter;
+pub use crate::pncounter::PNCounter;
 pub use crate::set::GSet;
 
 use std::hash::Hash;
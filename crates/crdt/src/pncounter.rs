@@ -0,0 +1,570 @@
+use std::{
+    borrow::Borrow,
+    cmp::max,
+    collections::TryReserveError,
+    hash::{BuildHasher, Hash},
+    ops::{BitOr, BitOrAssign},
+};
+
+use anyhow::{ensure, Ok};
+use fxhash::FxBuildHasher;
+use smallvec::{smallvec, SmallVec};
+
+use crate::{griddle::Griddle, Decompose, Extract};
+
+/// A PNCounter is a counter that, unlike [`GCounter`], supports both increment and decrement while
+/// remaining a state-based CRDT. This is also a named data type meaning that replicas who share
+/// this data type must be uniquely identified.
+///
+/// # Implementation
+///
+/// Each replica id maps to a pair of grow-only tallies, `(increments, decrements)`, backed by the
+/// same incremental-resize hash map ([`Griddle`]) used by [`GCounter`]. `count()` is the difference
+/// of the two sums. Because each component only ever grows, joining is still the standard
+/// pointwise maximum used throughout this crate, so a `PNCounter` is exactly two [`GCounter`]s
+/// merged together rather than a new kind of lattice.
+///
+/// Following the standard library's [`HashSet<T, S = RandomState>`] pattern, the counter is generic
+/// over the [`BuildHasher`] `S`, defaulting to [`fxhash`] for local use while letting
+/// security-sensitive deployments opt into a keyed hasher via [`PNCounter::with_hasher`].
+///
+/// [`GCounter`]: crate::GCounter
+/// [`HashSet<T, S = RandomState>`]: std::collections::HashSet
+/// [`fxhash`]: fxhash
+///
+/// # Example
+///
+/// ```
+/// use crdt::PNCounter;
+///
+/// let mut counter = PNCounter::new();
+///
+/// counter.increment(&"a");
+/// counter.increment(&"a");
+/// counter.decrement(&"a");
+///
+/// if counter.count() == 1 {
+///     println!("The PNCounter was incremented twice and decremented once");
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PNCounter<I, S = FxBuildHasher> {
+    inner: Griddle<I, (u64, u64), S>,
+}
+
+/// A view into the state of a [`PNCounter`]. They can be joined with any other [`PNCounter`] in
+/// order to synchronize. They are read-only but can be easily converted into a [`PNCounter`] using
+/// the trait [`From`].
+///
+/// [`From`]: std::convert::From
+#[derive(Clone)]
+pub struct Delta<'a, I, S = FxBuildHasher> {
+    counter: &'a PNCounter<I, S>,
+    elems: SmallVec<[(&'a I, &'a (u64, u64)); 1]>,
+}
+
+/// An owned counterpart of [`Delta`] that holds a clone of the counter's state rather than
+/// borrowing its source, so it can outlive the counter that produced it.
+#[derive(Clone, Debug)]
+pub struct OwnedDelta<I, S = FxBuildHasher> {
+    inner: Griddle<I, (u64, u64), S>,
+}
+
+impl<I, S> OwnedDelta<I, S> {
+    /// Returns the number of entries carried by the delta.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the delta carries no entries.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl<I> PNCounter<I> {
+    /// Creates a [`PNCounter`] set to the value of 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crdt::PNCounter;
+    ///
+    /// let counter: PNCounter<i32> = PNCounter::new();
+    /// assert_eq!(counter.count(), 0);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            inner: Griddle::default(),
+        }
+    }
+}
+
+impl<I, S> PNCounter<I, S> {
+    /// Creates an empty [`PNCounter`] that will use `hasher` to hash the replica ids.
+    ///
+    /// This mirrors [`HashSet::with_hasher`] and lets security-sensitive deployments opt into a
+    /// DoS-resistant or keyed hasher while keeping [`fxhash`] as the default for local use.
+    ///
+    /// [`HashSet::with_hasher`]: std::collections::HashSet::with_hasher
+    /// [`fxhash`]: fxhash
+    #[inline]
+    #[must_use]
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            inner: Griddle::with_hasher(hasher),
+        }
+    }
+}
+
+impl<I, S> PNCounter<I, S>
+where
+    I: Eq + Hash,
+    S: BuildHasher,
+{
+    /// Returns the count of the counter, i.e., the sum of increments minus the sum of decrements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crdt::PNCounter;
+    ///
+    /// let mut counter = PNCounter::new();
+    /// assert_eq!(counter.count(), 0);
+    ///
+    /// counter.increment(&"a");
+    /// counter.increment(&"a");
+    /// counter.decrement(&"a");
+    /// assert_eq!(counter.count(), 1);
+    /// ```
+    pub fn count(&self) -> i64 {
+        let (pos, neg) = self
+            .inner
+            .values()
+            .fold((0u64, 0u64), |(pos, neg), (p, n)| (pos + p, neg + n));
+
+        i64::try_from(pos).unwrap_or(i64::MAX) - i64::try_from(neg).unwrap_or(i64::MAX)
+    }
+
+    /// Returns the count, i.e., the increments minus the decrements, of a given `id`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crdt::PNCounter;
+    ///
+    /// let mut counter = PNCounter::new();
+    /// assert_eq!(counter.count_of(&"a"), None);
+    ///
+    /// counter.increment(&"a");
+    /// counter.increment(&"a");
+    /// counter.decrement(&"a");
+    /// assert_eq!(counter.count_of(&"a"), Some(1));
+    /// ```
+    pub fn count_of<Q: ?Sized + Hash + Eq>(&self, id: &Q) -> Option<i64>
+    where
+        I: Borrow<Q>,
+    {
+        self.inner
+            .get(id)
+            .map(|(pos, neg)| i64::try_from(*pos).unwrap_or(i64::MAX) - i64::try_from(*neg).unwrap_or(i64::MAX))
+    }
+
+    /// Transforms the `self` into a `Delta` object that contains its entire state.
+    pub fn as_delta(&self) -> Delta<'_, I, S> {
+        Delta {
+            counter: self,
+            elems: self.inner.iter().collect(),
+        }
+    }
+
+    /// Returns an owned snapshot of the whole counter, detached from `self` so it can be retained
+    /// or moved independently.
+    #[must_use]
+    pub fn snapshot(&self) -> Self
+    where
+        I: Clone,
+        S: Clone,
+    {
+        self.clone()
+    }
+
+    /// Transforms `self` into an [`OwnedDelta`] holding a clone of its entire state, which — unlike
+    /// [`PNCounter::as_delta`] — may outlive the counter that produced it.
+    pub fn as_owned_delta(&self) -> OwnedDelta<I, S>
+    where
+        I: Clone,
+        S: Clone,
+    {
+        OwnedDelta {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<I, S> PNCounter<I, S>
+where
+    I: Clone + Eq + Hash,
+    S: BuildHasher,
+{
+    /// Increments an `id` and returns a [`Delta`] that contains the `id` and its corresponding
+    /// pair of tallies. If the `id` is not present in the counter, a new entry is initialized with
+    /// `(1, 0)`.
+    pub fn increment(&mut self, id: &I) -> Delta<'_, I, S> {
+        match self.inner.get_mut(id) {
+            Some((pos, _)) => *pos += 1,
+            None => {
+                self.inner.insert(id.clone(), (1, 0));
+            }
+        };
+
+        let entry = self
+            .inner
+            .iter()
+            .find(|(key, _)| *key == id)
+            .expect("map must contain the key `id`");
+        Delta {
+            counter: self,
+            elems: smallvec![entry],
+        }
+    }
+
+    /// Decrements an `id` and returns a [`Delta`] that contains the `id` and its corresponding
+    /// pair of tallies. If the `id` is not present in the counter, a new entry is initialized with
+    /// `(0, 1)`.
+    pub fn decrement(&mut self, id: &I) -> Delta<'_, I, S> {
+        match self.inner.get_mut(id) {
+            Some((_, neg)) => *neg += 1,
+            None => {
+                self.inner.insert(id.clone(), (0, 1));
+            }
+        };
+
+        let entry = self
+            .inner
+            .iter()
+            .find(|(key, _)| *key == id)
+            .expect("map must contain the key `id`");
+        Delta {
+            counter: self,
+            elems: smallvec![entry],
+        }
+    }
+
+    /// Fallible counterpart to [`Decompose::join`] that reserves room for the incoming entries up
+    /// front and reports allocation failure via [`TryReserveError`] instead of aborting the
+    /// process. See [`GCounter::try_join`](crate::GCounter::try_join) for the rationale. As there,
+    /// only the staging buffer is reserved fallibly; inserting into the backing map still
+    /// allocates through the ordinary path, which aborts on failure.
+    pub fn try_join(&mut self, deltas: Vec<Delta<'_, I, S>>) -> Result<(), TryReserveError> {
+        let advances = |entry: &&(&I, &(u64, u64))| {
+            let (id, value) = **entry;
+            match self.inner.get(id) {
+                Some((pos, neg)) => value.0 > *pos || value.1 > *neg,
+                None => true,
+            }
+        };
+
+        let count = deltas
+            .iter()
+            .flat_map(|d| d.elems.iter())
+            .filter(advances)
+            .count();
+
+        let mut pending: Vec<(&I, (u64, u64))> = Vec::new();
+        pending.try_reserve(count)?;
+        pending.extend(
+            deltas
+                .iter()
+                .flat_map(|d| d.elems.iter())
+                .filter(advances)
+                .map(|(id, value)| (*id, **value)),
+        );
+
+        for (id, (pos, neg)) in pending {
+            match self.inner.get_mut(id) {
+                Some(local) => *local = (max(local.0, pos), max(local.1, neg)),
+                None => {
+                    self.inner.insert(id.clone(), (pos, neg));
+                }
+            }
+        }
+
+        std::result::Result::Ok(())
+    }
+}
+
+impl<I, S> PartialEq for PNCounter<I, S>
+where
+    I: Eq + Hash,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<I, S> From<Delta<'_, I, S>> for PNCounter<I, S>
+where
+    I: Clone + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn from(value: Delta<'_, I, S>) -> Self {
+        Self {
+            inner: Griddle::from_iter(value.elems.into_iter().map(|(id, v)| (id.clone(), *v))),
+        }
+    }
+}
+
+impl<I, S> From<OwnedDelta<I, S>> for PNCounter<I, S> {
+    fn from(value: OwnedDelta<I, S>) -> Self {
+        Self { inner: value.inner }
+    }
+}
+
+/// Joins `rhs` into `self`, the least-upper-bound merge that takes the pointwise maximum of every
+/// replica's tallies. This is exactly [`Decompose::join`] spelled as the `|=` operator.
+impl<I, S> BitOrAssign for PNCounter<I, S>
+where
+    I: Clone + Eq + Hash,
+    S: BuildHasher,
+{
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.join(rhs.split());
+    }
+}
+
+/// Returns the least-upper-bound merge of two counters, i.e. the pointwise maximum of their
+/// tallies, so `a | b` reads as the join of both states.
+impl<I, S> BitOr for PNCounter<I, S>
+where
+    I: Clone + Eq + Hash,
+    S: BuildHasher,
+{
+    type Output = PNCounter<I, S>;
+
+    fn bitor(mut self, rhs: Self) -> Self::Output {
+        self |= rhs;
+        self
+    }
+}
+
+impl<I, S> Decompose<I> for PNCounter<I, S>
+where
+    I: Eq + Hash,
+    S: BuildHasher,
+{
+    type Decomposition<'a>
+        = Delta<'a, I, S>
+    where
+        I: 'a,
+        S: 'a;
+
+    fn split(&self) -> Vec<Self::Decomposition<'_>> {
+        self.inner
+            .iter()
+            .map(|entry| Delta {
+                counter: self,
+                elems: smallvec![entry],
+            })
+            .collect()
+    }
+
+    fn join(&mut self, deltas: Vec<Self::Decomposition<'_>>)
+    where
+        I: Clone,
+    {
+        deltas
+            .into_iter()
+            .flat_map(|d| d.elems)
+            .for_each(|(id, remote_value)| {
+                match self.inner.get_mut(id) {
+                    Some(local_value) => {
+                        *local_value = (max(local_value.0, remote_value.0), max(local_value.1, remote_value.1));
+                    }
+                    None => {
+                        self.inner.insert(id.clone(), *remote_value);
+                    }
+                };
+            })
+    }
+
+    fn difference<'a>(&'a self, remote: &'a Self) -> Self::Decomposition<'a> {
+        Delta {
+            counter: self,
+            elems: self
+                .inner
+                .iter()
+                .filter(|(id, v)| match remote.inner.get(id) {
+                    Some(r) => v.0 > r.0 || v.1 > r.1,
+                    None => true,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<'b, I, S> Extract<(&'b I, &'b (u64, u64))> for PNCounter<I, S>
+where
+    I: Hash,
+{
+    type Decomposition<'a>
+        = Delta<'b, I, S>
+    where
+        I: 'a,
+        S: 'a,
+        S: 'b;
+
+    fn extract(delta: &Self::Decomposition<'b>) -> anyhow::Result<(&'b I, &'b (u64, u64))> {
+        ensure!(delta.elems.len() == 1);
+        match delta.elems.first() {
+            Some(value) => Ok(*value),
+            None => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::griddle::Griddle;
+
+    use crate::{Decompose, Extract, PNCounter};
+
+    #[test]
+    fn incrementation_and_decrementation_test() {
+        let mut counter = PNCounter::new();
+        assert_eq!(counter.count(), 0, "empty counter different than 0");
+
+        counter.increment(&"a");
+        counter.increment(&"b");
+        counter.increment(&"a");
+        counter.decrement(&"a");
+
+        assert_eq!(counter.count(), 1);
+        assert_eq!(counter.count_of(&"a"), Some(1));
+        assert_eq!(counter.count_of(&"b"), Some(1));
+        assert_eq!(counter.count_of(&"c"), None);
+    }
+
+    #[test]
+    fn irredudant_join_decomposition_test() {
+        let mut counter = PNCounter::new();
+
+        counter.increment(&"a");
+        counter.increment(&"b");
+        counter.decrement(&"a");
+
+        let irredundant_join_decomposition = counter.split();
+        assert_eq!(irredundant_join_decomposition.len(), 2);
+
+        assert!(irredundant_join_decomposition
+            .iter()
+            .all(|d| d.elems.len() == 1));
+
+        let mut remote = PNCounter::new();
+        remote.join(irredundant_join_decomposition);
+
+        assert_eq!(counter, remote);
+    }
+
+    #[test]
+    fn difference_test() {
+        let mut local = PNCounter {
+            inner: Griddle::from_iter([("a", (2, 1)), ("b", (3, 0)), ("c", (1, 0)), ("e", (1, 0))]),
+        };
+
+        let mut remote = PNCounter {
+            inner: Griddle::from_iter([("a", (2, 1)), ("b", (1, 0)), ("d", (1, 0)), ("e", (3, 0))]),
+        };
+
+        let actual_local_diff = PNCounter::from(local.difference(&remote));
+        let expected_local_diff = PNCounter {
+            inner: Griddle::from_iter([("b", (3, 0)), ("c", (1, 0))]),
+        };
+        assert_eq!(actual_local_diff, expected_local_diff);
+
+        let actual_remote_diff = PNCounter::from(remote.difference(&local));
+        let expected_remote_diff = PNCounter {
+            inner: Griddle::from_iter([("d", (1, 0)), ("e", (3, 0))]),
+        };
+        assert_eq!(actual_remote_diff, expected_remote_diff);
+
+        local.join(vec![actual_remote_diff.as_delta()]);
+        remote.join(vec![actual_local_diff.as_delta()]);
+        assert_eq!(local, remote);
+
+        let local_diff = PNCounter::from(local.difference(&remote));
+        assert_eq!(
+            local_diff.count(),
+            0,
+            "difference between equal counters different than 0"
+        );
+    }
+
+    #[test]
+    fn try_join_matches_join() {
+        let mut counter = PNCounter::new();
+        counter.increment(&"a");
+        counter.increment(&"b");
+        counter.decrement(&"a");
+
+        let mut joined = PNCounter::new();
+        joined.join(counter.split());
+
+        let mut tried = PNCounter::new();
+        tried.try_join(counter.split()).expect("reservation failed");
+
+        assert_eq!(joined, tried);
+        assert_eq!(tried, counter);
+    }
+
+    #[test]
+    fn bitor_matches_join() {
+        let local = PNCounter {
+            inner: Griddle::from_iter([("a", (2, 0)), ("b", (3, 1)), ("c", (1, 0))]),
+        };
+
+        let remote = PNCounter {
+            inner: Griddle::from_iter([("a", (2, 0)), ("b", (1, 2)), ("d", (1, 0))]),
+        };
+
+        let mut joined = local.clone();
+        joined.join(remote.split());
+
+        let mut merged = local.clone();
+        merged |= remote.clone();
+        assert_eq!(merged, joined);
+
+        assert_eq!(local | remote, joined);
+    }
+
+    #[test]
+    fn extraction_test() {
+        let mut counter = PNCounter::new();
+
+        let empty_delta = counter.as_delta();
+        let extraction = PNCounter::extract(&empty_delta);
+        assert!(
+            extraction.is_err(),
+            "extraction is working with empty deltas"
+        );
+
+        let delta = counter.increment(&"a");
+        let extraction = PNCounter::extract(&delta);
+        let expected = delta
+            .elems
+            .first()
+            .expect("expected should contain at least one element");
+        assert!(extraction.is_ok_and(|v| v == *expected));
+
+        counter.increment(&"b");
+        let large_delta = counter.as_delta();
+        let extraction = PNCounter::extract(&large_delta);
+        assert!(
+            extraction.is_err(),
+            "extraction is working with large deltas"
+        );
+    }
+}
@@ -2,10 +2,16 @@ use std::{
     collections::{HashMap, HashSet},
     hash::Hash,
     mem,
+    ops::{BitAnd, BitOr, BitXor, Sub},
 };
 
 use either::*;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use hamt::Hamt;
+
+mod hamt;
 
 pub trait Decompose {
     type Decomposition;
@@ -30,6 +36,112 @@ pub trait Measure {
     fn false_matches(&self, other: &Self) -> usize;
 }
 
+/// The serialized byte cost of a value, used by [`Measure::size_of`] to estimate the wire cost of
+/// a [`GSet`]/[`AWSet`] decomposition without hard-coding the bookkeeping to `String` elements.
+pub trait SizeOf {
+    fn size_of(&self) -> usize;
+}
+
+impl SizeOf for String {
+    fn size_of(&self) -> usize {
+        self.len()
+    }
+}
+
+impl SizeOf for &str {
+    fn size_of(&self) -> usize {
+        self.len()
+    }
+}
+
+impl SizeOf for i8 {
+    fn size_of(&self) -> usize {
+        mem::size_of::<Self>()
+    }
+}
+
+impl SizeOf for i16 {
+    fn size_of(&self) -> usize {
+        mem::size_of::<Self>()
+    }
+}
+
+impl SizeOf for i32 {
+    fn size_of(&self) -> usize {
+        mem::size_of::<Self>()
+    }
+}
+
+impl SizeOf for i64 {
+    fn size_of(&self) -> usize {
+        mem::size_of::<Self>()
+    }
+}
+
+impl SizeOf for i128 {
+    fn size_of(&self) -> usize {
+        mem::size_of::<Self>()
+    }
+}
+
+impl SizeOf for isize {
+    fn size_of(&self) -> usize {
+        mem::size_of::<Self>()
+    }
+}
+
+impl SizeOf for u8 {
+    fn size_of(&self) -> usize {
+        mem::size_of::<Self>()
+    }
+}
+
+impl SizeOf for u16 {
+    fn size_of(&self) -> usize {
+        mem::size_of::<Self>()
+    }
+}
+
+impl SizeOf for u32 {
+    fn size_of(&self) -> usize {
+        mem::size_of::<Self>()
+    }
+}
+
+impl SizeOf for u64 {
+    fn size_of(&self) -> usize {
+        mem::size_of::<Self>()
+    }
+}
+
+impl SizeOf for u128 {
+    fn size_of(&self) -> usize {
+        mem::size_of::<Self>()
+    }
+}
+
+impl SizeOf for usize {
+    fn size_of(&self) -> usize {
+        mem::size_of::<Self>()
+    }
+}
+
+impl<T, const N: usize> SizeOf for [T; N]
+where
+    T: SizeOf,
+{
+    fn size_of(&self) -> usize {
+        self.iter().map(SizeOf::size_of).sum()
+    }
+}
+
+/// A globally-unique identifier for an [`AWSet`] element: the replica that minted it paired with
+/// that replica's local, monotonically increasing counter. Unlike a random `u64`, two replicas can
+/// never mint the same dot without coordinating, so [`Decompose::join`] can safely union dot maps
+/// without risking two different values silently colliding under the same key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Dot(u64, u64);
+
 #[derive(PartialEq, Eq, Debug, Default)]
 pub struct Elements<'a, T> {
     elems: Vec<&'a T>,
@@ -49,7 +161,11 @@ impl<'a, T> Iterator for Elements<'a, T> {
     }
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "T: Serialize + Eq + Hash",
+    deserialize = "T: Deserialize<'de> + Eq + Hash"
+))]
 pub struct GSet<T> {
     base: HashSet<T>,
 }
@@ -82,6 +198,24 @@ where
     pub fn contains(&self, value: &T) -> bool {
         self.base.contains(value)
     }
+
+    /// Returns `true` if `self` and `other` share no elements.
+    #[inline]
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.base.is_disjoint(&other.base)
+    }
+
+    /// Returns `true` if every element of `self` is also in `other`.
+    #[inline]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.base.is_subset(&other.base)
+    }
+
+    /// Returns `true` if `self` contains every element of `other`.
+    #[inline]
+    pub fn is_superset(&self, other: &Self) -> bool {
+        self.base.is_superset(&other.base)
+    }
 }
 
 impl<T> GSet<T>
@@ -96,6 +230,30 @@ where
         }
     }
 
+    /// Returns an iterator over the elements present in `self`, `other`, or both.
+    pub fn union<'a>(&'a self, other: &'a Self) -> Elements<'a, T> {
+        Elements {
+            elems: self.base.union(&other.base).collect(),
+            idx: 0,
+        }
+    }
+
+    /// Returns an iterator over the elements present in both `self` and `other`.
+    pub fn intersection<'a>(&'a self, other: &'a Self) -> Elements<'a, T> {
+        Elements {
+            elems: self.base.intersection(&other.base).collect(),
+            idx: 0,
+        }
+    }
+
+    /// Returns an iterator over the elements present in `self` or `other`, but not both.
+    pub fn symmetric_difference<'a>(&'a self, other: &'a Self) -> Elements<'a, T> {
+        Elements {
+            elems: self.base.symmetric_difference(&other.base).collect(),
+            idx: 0,
+        }
+    }
+
     pub fn insert(&mut self, value: T) -> Self {
         if self.base.insert(value.clone()) {
             Self {
@@ -155,13 +313,16 @@ where
     }
 }
 
-impl Measure for GSet<String> {
+impl<T> Measure for GSet<T>
+where
+    T: Clone + Eq + Hash + SizeOf,
+{
     fn len(replica: &Self) -> usize {
         replica.len()
     }
 
     fn size_of(replica: &Self) -> usize {
-        replica.elements().map(String::len).sum()
+        replica.elements().map(SizeOf::size_of).sum()
     }
 
     fn false_matches(&self, other: &Self) -> usize {
@@ -180,6 +341,62 @@ where
 
 impl<T> Eq for GSet<T> where T: Eq + Hash {}
 
+/// Returns the union of two sets, `self | other`.
+impl<T> BitOr for &GSet<T>
+where
+    T: Clone + Eq + Hash,
+{
+    type Output = GSet<T>;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        GSet {
+            base: &self.base | &rhs.base,
+        }
+    }
+}
+
+/// Returns the intersection of two sets, `self & other`.
+impl<T> BitAnd for &GSet<T>
+where
+    T: Clone + Eq + Hash,
+{
+    type Output = GSet<T>;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        GSet {
+            base: &self.base & &rhs.base,
+        }
+    }
+}
+
+/// Returns the symmetric difference of two sets, `self ^ other`.
+impl<T> BitXor for &GSet<T>
+where
+    T: Clone + Eq + Hash,
+{
+    type Output = GSet<T>;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        GSet {
+            base: &self.base ^ &rhs.base,
+        }
+    }
+}
+
+/// Returns what `self` has that `other` lacks, `self - other`.
+impl<T> Sub for &GSet<T>
+where
+    T: Clone + Eq + Hash,
+{
+    type Output = GSet<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        GSet {
+            base: &self.base - &rhs.base,
+        }
+    }
+}
+
 #[cfg(test)]
 mod gset {
     use super::*;
@@ -235,166 +452,2053 @@ mod gset {
         let diff = local.difference(&remote);
         assert!(diff.is_empty());
     }
+
+    #[test]
+    fn test_relational_ops() {
+        let local = GSet {
+            base: HashSet::from_iter(0..=2),
+        };
+        let remote = GSet {
+            base: HashSet::from_iter(2..=4),
+        };
+
+        assert!(!local.is_disjoint(&remote));
+        let far = GSet {
+            base: HashSet::from([10]),
+        };
+        assert!(far.is_disjoint(&remote));
+        assert!(!local.is_subset(&remote));
+        assert!(!local.is_superset(&remote));
+
+        let subset = GSet {
+            base: HashSet::from_iter(0..=1),
+        };
+        assert!(subset.is_subset(&local));
+        assert!(local.is_superset(&subset));
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let local = GSet {
+            base: HashSet::from_iter(0..=2),
+        };
+        let remote = GSet {
+            base: HashSet::from_iter(2..=4),
+        };
+
+        assert!(local.union(&remote).all(|v| (0..=4).contains(v)));
+        assert_eq!(local.union(&remote).count(), 5);
+
+        assert_eq!(local.intersection(&remote).count(), 1);
+        assert!(local.intersection(&remote).all(|v| *v == 2));
+
+        assert_eq!(local.symmetric_difference(&remote).count(), 4);
+        assert!(!local.symmetric_difference(&remote).any(|v| *v == 2));
+    }
+
+    #[test]
+    fn test_operators() {
+        let local = GSet {
+            base: HashSet::from_iter(0..=2),
+        };
+        let remote = GSet {
+            base: HashSet::from_iter(2..=4),
+        };
+
+        assert_eq!(
+            &local | &remote,
+            GSet {
+                base: HashSet::from_iter(0..=4)
+            }
+        );
+        assert_eq!(
+            &local & &remote,
+            GSet {
+                base: HashSet::from([2])
+            }
+        );
+        assert_eq!(
+            &local ^ &remote,
+            GSet {
+                base: HashSet::from([0, 1, 3, 4])
+            }
+        );
+        assert_eq!(&local - &remote, local.difference(&remote));
+    }
+
+    #[test]
+    fn test_measure_on_non_string_payload() {
+        let local: GSet<u64> = GSet {
+            base: HashSet::from_iter(0..=2),
+        };
+        let remote: GSet<u64> = GSet {
+            base: HashSet::from_iter(2..=4),
+        };
+
+        assert_eq!(Measure::len(&local), 3);
+        assert_eq!(
+            Measure::size_of(&local),
+            local.elements().count() * mem::size_of::<u64>()
+        );
+        assert_eq!(local.false_matches(&remote), 4);
+    }
 }
 
+/// A [`GSet`] backed by a persistent [`Hamt`] instead of a plain `HashSet`.
+///
+/// [`GSet::split`] allocates a fresh `HashSet` per element and [`GSet::clone`] deep-copies the
+/// whole `base`, so a replica of N elements costs `O(N)` per decomposition round — the dominant
+/// cost of `BloomBuckets::sync`, which clones and splits both replicas on every sync. Because the
+/// [`Hamt`]'s interior nodes are reference-counted and shared, `clone` here is `O(1)` and `split`
+/// only copies the spine touched by each element rather than the entire set. The trait surface
+/// ([`Decompose`], [`Extract`], [`Measure`]) matches [`GSet`] exactly, so this is a drop-in
+/// alternative for benchmarking allocation and throughput against it.
+///
+/// [`GSet::split`]: Decompose::split
+/// [`GSet::clone`]: Clone::clone
 #[derive(Clone, Debug, Default)]
-pub struct AWSet<T> {
-    inserted: HashMap<u64, T>,
-    removed: HashSet<u64>,
+pub struct GSetHamt<T> {
+    base: Hamt<T, ()>,
 }
 
-impl<T> AWSet<T> {
+impl<T> GSetHamt<T> {
     #[inline]
     #[must_use]
     pub fn new() -> Self {
-        Self {
-            inserted: HashMap::new(),
-            removed: HashSet::new(),
-        }
-    }
-
-    #[inline]
-    pub fn elements(&self) -> Elements<'_, T> {
-        Elements {
-            elems: self
-                .inserted
-                .iter()
-                .filter_map(|(id, v)| (!self.removed.contains(id)).then_some(v))
-                .collect(),
-            idx: 0,
-        }
+        Self { base: Hamt::new() }
     }
 
     #[inline]
     pub fn is_empty(&self) -> bool {
-        !self.inserted.keys().any(|id| !self.removed.contains(id))
+        self.base.is_empty()
     }
 
     #[inline]
     pub fn len(&self) -> usize {
-        self.inserted
-            .keys()
-            .filter(|id| !self.removed.contains(id))
-            .count()
-    }
-
-    fn uid(&self) -> u64 {
-        let mut rng = rand::thread_rng();
-        let mut id = rng.gen();
-
-        while self.inserted.contains_key(&id) {
-            id = rng.gen();
-        }
-
-        id
+        self.base.len()
     }
 }
 
-impl<T> AWSet<T>
+impl<T> GSetHamt<T>
 where
-    T: Eq + Hash,
+    T: Clone + Eq + Hash,
 {
     #[inline]
     pub fn contains(&self, value: &T) -> bool {
-        self.inserted
-            .iter()
-            .any(|(id, v)| value == v && !self.removed.contains(id))
+        self.base.contains_key(value)
     }
 
-    pub fn remove(&mut self, value: &T) -> Self {
-        let ids = self
-            .inserted
-            .iter()
-            .filter_map(|(id, v)| (value == v && !self.removed.contains(id)).then_some(*id))
-            .collect::<HashSet<_>>();
-
-        ids.iter().for_each(|id| {
-            self.removed.insert(*id);
-        });
-
-        Self {
-            inserted: HashMap::new(),
-            removed: ids,
+    #[inline]
+    pub fn elements(&self) -> Elements<'_, T> {
+        Elements {
+            elems: self.base.iter().map(|(v, ())| v).collect(),
+            idx: 0,
         }
     }
-}
 
-impl<T> AWSet<T>
-where
-    T: Clone + Eq + Hash,
-{
     pub fn insert(&mut self, value: T) -> Self {
-        let id = self.uid();
-        self.inserted.insert(id, value.clone());
-
-        Self {
-            inserted: HashMap::from([(id, value)]),
-            removed: HashSet::new(),
+        if self.base.insert(value.clone(), ()).is_none() {
+            Self {
+                base: Hamt::from_iter([(value, ())]),
+            }
+        } else {
+            Self { base: Hamt::new() }
         }
     }
 }
 
-impl<T> Decompose for AWSet<T>
+impl<T> Decompose for GSetHamt<T>
 where
     T: Clone + Eq + Hash,
 {
-    type Decomposition = AWSet<T>;
+    type Decomposition = GSetHamt<T>;
 
     fn split(&self) -> Vec<Self::Decomposition> {
-        let inserted = self.inserted.iter().map(|(id, v)| Self {
-            inserted: HashMap::from([(*id, v.clone())]),
-            removed: HashSet::new(),
-        });
-
-        let removed = self.removed.iter().cloned().map(|id| Self {
-            inserted: HashMap::new(),
-            removed: HashSet::from([id]),
-        });
-
-        inserted.chain(removed).collect()
+        self.base
+            .iter()
+            .map(|(value, ())| Self {
+                base: Hamt::from_iter([(value.clone(), ())]),
+            })
+            .collect()
     }
 
     fn join(&mut self, deltas: Vec<Self::Decomposition>) {
-        deltas.into_iter().for_each(|delta| {
-            self.inserted.extend(delta.inserted);
-            self.removed.extend(delta.removed);
-        })
+        deltas
+            .into_iter()
+            .for_each(|delta| self.base.extend(delta.base.iter().map(|(v, ())| (v.clone(), ()))))
     }
 
     fn difference(&self, remote: &Self::Decomposition) -> Self::Decomposition {
         Self {
-            inserted: self
+            base: self
+                .base
+                .iter()
+                .filter(|(v, ())| !remote.base.contains_key(*v))
+                .map(|(v, ())| (v.clone(), ()))
+                .collect(),
+        }
+    }
+}
+
+impl<T> Extract for GSetHamt<T>
+where
+    T: Clone + Eq + Hash,
+{
+    type Item = T;
+
+    fn extract(&self) -> Self::Item {
+        assert_eq!(
+            self.len(),
+            1,
+            "a join-decomposition should have a single item"
+        );
+
+        self.base.iter().next().map(|(v, ())| v.clone()).unwrap()
+    }
+}
+
+impl<T> Measure for GSetHamt<T>
+where
+    T: Clone + Eq + Hash + SizeOf,
+{
+    fn len(replica: &Self) -> usize {
+        replica.len()
+    }
+
+    fn size_of(replica: &Self) -> usize {
+        replica.elements().map(SizeOf::size_of).sum()
+    }
+
+    fn false_matches(&self, other: &Self) -> usize {
+        self.elements().filter(|v| !other.contains(v)).count()
+            + other.elements().filter(|v| !self.contains(v)).count()
+    }
+}
+
+impl<T> PartialEq for GSetHamt<T>
+where
+    T: Clone + Eq + Hash,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.base == other.base
+    }
+}
+
+impl<T> Eq for GSetHamt<T> where T: Clone + Eq + Hash {}
+
+#[cfg(test)]
+mod gset_hamt {
+    use super::*;
+
+    #[test]
+    fn test_split_and_join() {
+        let mut splittable = GSetHamt::new();
+
+        splittable.insert(1);
+        splittable.insert(2);
+        splittable.insert(2);
+        assert_eq!(splittable.len(), 2);
+
+        let decompositions = splittable.split();
+        assert_eq!(decompositions.len(), splittable.len());
+
+        let mut joinable = GSetHamt::new();
+
+        joinable.join(decompositions);
+        assert_eq!(joinable.len(), splittable.len());
+        assert!(joinable.contains(&1));
+        assert!(joinable.contains(&2));
+    }
+
+    #[test]
+    fn test_difference() {
+        let local = GSetHamt {
+            base: Hamt::from_iter((0..=2).map(|v| (v, ()))),
+        };
+        let remote = GSetHamt {
+            base: Hamt::from_iter((2..=4).map(|v| (v, ()))),
+        };
+
+        let diff = local.difference(&remote);
+        assert!(diff.contains(&0));
+        assert!(diff.contains(&1));
+        assert!(!diff.contains(&2));
+        assert!(!diff.contains(&3));
+        assert!(!diff.contains(&4));
+    }
+
+    #[test]
+    fn test_clone_is_cheap_and_independent() {
+        let mut original = GSetHamt::new();
+        original.insert(1);
+        original.insert(2);
+
+        let snapshot = original.clone();
+        original.insert(3);
+
+        assert_eq!(snapshot.len(), 2);
+        assert!(!snapshot.contains(&3));
+        assert_eq!(original.len(), 3);
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))]
+pub struct AWSet<T> {
+    replica_id: u64,
+    counter: u64,
+    inserted: HashMap<Dot, T>,
+    removed: HashSet<Dot>,
+}
+
+impl<T> AWSet<T> {
+    /// Creates an empty `AWSet` identified by a fresh, randomly chosen replica id.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_replica(rand::thread_rng().gen())
+    }
+
+    /// Creates an empty `AWSet` that mints dots under the given `replica_id`.
+    ///
+    /// Two `AWSet`s must use distinct replica ids if they are meant to evolve independently: dots
+    /// are only guaranteed unique across replicas that don't share one.
+    #[inline]
+    #[must_use]
+    pub fn with_replica(replica_id: u64) -> Self {
+        Self {
+            replica_id,
+            counter: 0,
+            inserted: HashMap::new(),
+            removed: HashSet::new(),
+        }
+    }
+
+    #[inline]
+    pub fn elements(&self) -> Elements<'_, T> {
+        Elements {
+            elems: self
                 .inserted
                 .iter()
-                .filter(|(id, _)| !remote.inserted.contains_key(id))
-                .map(|(id, v)| (*id, v.clone()))
-                .collect(),
-            removed: self.removed.difference(&remote.removed).cloned().collect(),
+                .filter_map(|(id, v)| (!self.removed.contains(id)).then_some(v))
+                .collect(),
+            idx: 0,
+        }
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !self.inserted.keys().any(|id| !self.removed.contains(id))
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inserted
+            .keys()
+            .filter(|id| !self.removed.contains(id))
+            .count()
+    }
+
+    /// Re-assigns the replica identity used to mint new dots, leaving the elements already
+    /// observed untouched. Needed when forking a snapshot (e.g. via [`Clone::clone`]) into two
+    /// replicas that must diverge from here on, since they would otherwise keep minting identical
+    /// dots for different values.
+    #[inline]
+    #[must_use]
+    pub fn fork(mut self, replica_id: u64) -> Self {
+        self.replica_id = replica_id;
+        self.counter = 0;
+        self
+    }
+
+    fn next_dot(&mut self) -> Dot {
+        self.counter += 1;
+        Dot(self.replica_id, self.counter)
+    }
+}
+
+impl<T> Default for AWSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> AWSet<T>
+where
+    T: Eq + Hash,
+{
+    #[inline]
+    pub fn contains(&self, value: &T) -> bool {
+        self.inserted
+            .iter()
+            .any(|(id, v)| value == v && !self.removed.contains(id))
+    }
+
+    /// Returns `true` if `self` and `other` observe no common elements.
+    ///
+    /// This compares the observable elements ([`AWSet::elements`]/[`AWSet::contains`]), not the
+    /// raw `inserted`/`removed` maps, so the result matches the set the user actually sees.
+    pub fn is_disjoint(&self, other: &Self) -> bool
+    where
+        T: Clone,
+    {
+        self.elements().all(|v| !other.contains(v))
+    }
+
+    /// Returns `true` if every element `self` observes is also observed by `other`.
+    pub fn is_subset(&self, other: &Self) -> bool
+    where
+        T: Clone,
+    {
+        self.elements().all(|v| other.contains(v))
+    }
+
+    /// Returns `true` if `self` observes every element `other` does.
+    pub fn is_superset(&self, other: &Self) -> bool
+    where
+        T: Clone,
+    {
+        other.is_subset(self)
+    }
+
+    pub fn remove(&mut self, value: &T) -> Self {
+        let ids = self
+            .inserted
+            .iter()
+            .filter_map(|(id, v)| (value == v && !self.removed.contains(id)).then_some(*id))
+            .collect::<HashSet<_>>();
+
+        ids.iter().for_each(|id| {
+            self.removed.insert(*id);
+        });
+
+        Self {
+            replica_id: self.replica_id,
+            counter: self.counter,
+            inserted: HashMap::new(),
+            removed: ids,
+        }
+    }
+}
+
+impl<T> AWSet<T>
+where
+    T: Clone + Eq + Hash,
+{
+    pub fn insert(&mut self, value: T) -> Self {
+        let id = self.next_dot();
+        self.inserted.insert(id, value.clone());
+
+        Self {
+            replica_id: self.replica_id,
+            counter: self.counter,
+            inserted: HashMap::from([(id, value)]),
+            removed: HashSet::new(),
+        }
+    }
+
+    /// Returns a fresh [`AWSet`] observing every element `self` or `other` observes.
+    ///
+    /// The result is built by re-inserting the observed elements under fresh ids rather than
+    /// merging `inserted`/`removed`, so it matches the set the user actually sees instead of the
+    /// raw CRDT state.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = Self::new();
+        self.elements()
+            .chain(other.elements())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .for_each(|v| {
+                result.insert(v.clone());
+            });
+        result
+    }
+
+    /// Returns a fresh [`AWSet`] observing only the elements both `self` and `other` observe.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut result = Self::new();
+        self.elements()
+            .filter(|v| other.contains(v))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .for_each(|v| {
+                result.insert(v.clone());
+            });
+        result
+    }
+
+    /// Returns a fresh [`AWSet`] observing the elements `self` or `other` observes, but not both.
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        let mut result = Self::new();
+        self.elements()
+            .filter(|v| !other.contains(v))
+            .chain(other.elements().filter(|v| !self.contains(v)))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .for_each(|v| {
+                result.insert(v.clone());
+            });
+        result
+    }
+}
+
+/// Returns the union of two sets, `self | other`, rebuilt under fresh ids.
+impl<T> BitOr for &AWSet<T>
+where
+    T: Clone + Eq + Hash,
+{
+    type Output = AWSet<T>;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+/// Returns the intersection of two sets, `self & other`, rebuilt under fresh ids.
+impl<T> BitAnd for &AWSet<T>
+where
+    T: Clone + Eq + Hash,
+{
+    type Output = AWSet<T>;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(rhs)
+    }
+}
+
+/// Returns the symmetric difference of two sets, `self ^ other`, rebuilt under fresh ids.
+impl<T> BitXor for &AWSet<T>
+where
+    T: Clone + Eq + Hash,
+{
+    type Output = AWSet<T>;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        self.symmetric_difference(rhs)
+    }
+}
+
+/// Returns what `self` observes that `other` doesn't, `self - other`, rebuilt under fresh ids.
+///
+/// Unlike [`Decompose::difference`], which computes the network delta over the raw `inserted`/
+/// `removed` maps, this compares observable elements so the result matches the set the user
+/// actually sees.
+impl<T> Sub for &AWSet<T>
+where
+    T: Clone + Eq + Hash,
+{
+    type Output = AWSet<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut result = AWSet::new();
+        self.elements()
+            .filter(|v| !rhs.contains(v))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .for_each(|v| {
+                result.insert(v.clone());
+            });
+        result
+    }
+}
+
+impl<T> Decompose for AWSet<T>
+where
+    T: Clone + Eq + Hash,
+{
+    type Decomposition = AWSet<T>;
+
+    fn split(&self) -> Vec<Self::Decomposition> {
+        let inserted = self.inserted.iter().map(|(id, v)| Self {
+            replica_id: self.replica_id,
+            counter: self.counter,
+            inserted: HashMap::from([(*id, v.clone())]),
+            removed: HashSet::new(),
+        });
+
+        let removed = self.removed.iter().cloned().map(|id| Self {
+            replica_id: self.replica_id,
+            counter: self.counter,
+            inserted: HashMap::new(),
+            removed: HashSet::from([id]),
+        });
+
+        inserted.chain(removed).collect()
+    }
+
+    fn join(&mut self, deltas: Vec<Self::Decomposition>) {
+        deltas.into_iter().for_each(|delta| {
+            self.inserted.extend(delta.inserted);
+            self.removed.extend(delta.removed);
+        })
+    }
+
+    fn difference(&self, remote: &Self::Decomposition) -> Self::Decomposition {
+        Self {
+            replica_id: self.replica_id,
+            counter: self.counter,
+            inserted: self
+                .inserted
+                .iter()
+                .filter(|(id, _)| !remote.inserted.contains_key(id))
+                .map(|(id, v)| (*id, v.clone()))
+                .collect(),
+            removed: self.removed.difference(&remote.removed).cloned().collect(),
+        }
+    }
+}
+
+impl<T> Extract for AWSet<T>
+where
+    T: Clone + Eq + Hash,
+{
+    type Item = Either<(Dot, T), Dot>;
+
+    fn extract(&self) -> Self::Item {
+        if self.removed.is_empty() {
+            assert_eq!(
+                self.inserted.len(),
+                1,
+                "a join-decomposition should have a single item"
+            );
+
+            Left(
+                self.inserted
+                    .iter()
+                    .map(|(id, v)| (*id, v.clone()))
+                    .next()
+                    .unwrap(),
+            )
+        } else {
+            assert_eq!(
+                self.removed.len(),
+                1,
+                "a join-decomposition should have a single item"
+            );
+
+            Right(self.removed.iter().cloned().next().unwrap())
+        }
+    }
+}
+
+impl<T> Measure for AWSet<T>
+where
+    T: Clone + Eq + Hash + SizeOf,
+{
+    fn len(replica: &Self) -> usize {
+        replica.inserted.len() + replica.removed.len()
+    }
+
+    fn size_of(replica: &Self) -> usize {
+        replica.inserted.len() * mem::size_of::<Dot>()
+            + replica.inserted.values().map(SizeOf::size_of).sum::<usize>()
+            + replica.removed.len() * mem::size_of::<Dot>()
+    }
+
+    fn false_matches(&self, other: &Self) -> usize {
+        self.elements().filter(|v| !other.contains(v)).count()
+            + other.elements().filter(|v| !self.contains(v)).count()
+    }
+}
+
+impl<T> PartialEq for AWSet<T>
+where
+    T: Eq + Hash,
+{
+    fn eq(&self, other: &Self) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+
+        self.inserted
+            .iter()
+            .filter_map(|(id, v)| (!self.removed.contains(id)).then_some(v))
+            .all(|id| other.contains(id))
+    }
+}
+
+impl<T> Eq for AWSet<T> where T: Eq + Hash {}
+
+#[cfg(test)]
+mod awset {
+    use super::*;
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut awset = AWSet::new();
+        assert_eq!(awset.len(), 0);
+        assert!(awset.is_empty());
+
+        awset.insert(1);
+        awset.insert(2);
+        awset.insert(3);
+        assert_eq!(awset.len(), 3);
+        assert!(!awset.is_empty());
+
+        awset.remove(&2);
+        awset.remove(&2);
+        awset.remove(&4);
+        assert_eq!(awset.len(), 2);
+
+        awset.insert(2);
+        awset.insert(4);
+        assert_eq!(awset.len(), 4);
+    }
+
+    #[test]
+    fn test_elements() {
+        let mut awset = AWSet::new();
+        awset.insert(1);
+        awset.insert(2);
+        awset.insert(3);
+
+        assert!(awset.elements().all(|v| vec![1, 2, 3].contains(v)));
+
+        awset.remove(&1);
+        awset.insert(3);
+        awset.remove(&3);
+
+        assert_eq!(awset.elements().next(), Some(&2));
+
+        awset.remove(&2);
+        assert_eq!(awset.elements().next(), None);
+    }
+
+    #[test]
+    fn test_split_and_join() {
+        let mut splittable = AWSet::new();
+
+        splittable.insert(1);
+        splittable.insert(2);
+        splittable.insert(3);
+        splittable.remove(&2);
+        splittable.remove(&4);
+
+        assert!(splittable.contains(&1));
+        assert!(splittable.contains(&3));
+
+        let decompositions = splittable.split();
+        assert_eq!(decompositions.len(), 4);
+
+        let mut joinable = AWSet::new();
+        joinable.join(decompositions);
+
+        assert_eq!(splittable, joinable);
+    }
+
+    #[test]
+    fn test_difference() {
+        let local = AWSet {
+            replica_id: 0,
+            counter: 5,
+            inserted: HashMap::from([
+                (Dot(0, 1), 1),
+                (Dot(0, 2), 3),
+                (Dot(0, 3), 2),
+                (Dot(0, 4), 4),
+                (Dot(0, 5), 10),
+            ]),
+            removed: HashSet::from([Dot(0, 1), Dot(0, 3)]),
+        };
+
+        let remote = AWSet {
+            replica_id: 0,
+            counter: 3,
+            inserted: HashMap::from([(Dot(0, 1), 1), (Dot(0, 2), 3), (Dot(0, 3), 2)]),
+            removed: HashSet::from([Dot(0, 1), Dot(0, 2)]),
+        };
+
+        let diff = local.difference(&remote);
+        assert_eq!(
+            diff.inserted,
+            HashMap::from([(Dot(0, 4), 4), (Dot(0, 5), 10)])
+        );
+        assert_eq!(diff.removed, HashSet::from([Dot(0, 3)]));
+    }
+
+    #[test]
+    fn test_difference_synced() {
+        let local = AWSet {
+            replica_id: 0,
+            counter: 5,
+            inserted: HashMap::from([
+                (Dot(0, 1), 1),
+                (Dot(0, 2), 3),
+                (Dot(0, 3), 2),
+                (Dot(0, 4), 4),
+                (Dot(0, 5), 10),
+            ]),
+            removed: HashSet::from([Dot(0, 1), Dot(0, 3)]),
+        };
+
+        let remote = local.clone();
+
+        assert_eq!(local, remote);
+
+        let diff = local.difference(&remote);
+        assert!(diff.inserted.is_empty());
+        assert!(diff.removed.is_empty());
+    }
+
+    #[test]
+    fn test_false_matches() {
+        let local = AWSet {
+            replica_id: 0,
+            counter: 5,
+            inserted: HashMap::from([
+                (Dot(0, 1), "1".to_string()),
+                (Dot(0, 4), "4".to_string()),
+                (Dot(0, 5), "10".to_string()),
+            ]),
+            removed: HashSet::from([Dot(0, 1), Dot(0, 4)]),
+        };
+
+        let remote = AWSet {
+            replica_id: 1,
+            counter: 3,
+            inserted: HashMap::from([
+                (Dot(1, 1), "1".to_string()),
+                (Dot(1, 2), "3".to_string()),
+                (Dot(1, 3), "2".to_string()),
+            ]),
+            removed: HashSet::from([Dot(1, 1), Dot(1, 2)]),
+        };
+
+        let local_elems = local.elements().collect::<HashSet<_>>();
+        let remote_elems = remote.elements().collect::<HashSet<_>>();
+        assert_eq!(
+            local.false_matches(&remote),
+            local_elems.symmetric_difference(&remote_elems).count()
+        )
+    }
+
+    #[test]
+    fn test_relational_ops() {
+        let mut local = AWSet::new();
+        local.insert(1);
+        local.insert(2);
+
+        let mut remote = AWSet::new();
+        remote.insert(2);
+        remote.insert(3);
+
+        assert!(!local.is_disjoint(&remote));
+        assert!(!local.is_subset(&remote));
+        assert!(!local.is_superset(&remote));
+
+        let mut far = AWSet::new();
+        far.insert(10);
+        assert!(local.is_disjoint(&far));
+
+        let mut subset = AWSet::new();
+        subset.insert(1);
+        assert!(subset.is_subset(&local));
+        assert!(local.is_superset(&subset));
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let mut local = AWSet::new();
+        local.insert(1);
+        local.insert(2);
+
+        let mut remote = AWSet::new();
+        remote.insert(2);
+        remote.insert(3);
+
+        let union = local.union(&remote);
+        assert_eq!(union.len(), 3);
+        assert!([1, 2, 3].iter().all(|v| union.contains(v)));
+
+        let intersection = local.intersection(&remote);
+        assert_eq!(intersection.len(), 1);
+        assert!(intersection.contains(&2));
+
+        let symmetric_difference = local.symmetric_difference(&remote);
+        assert_eq!(symmetric_difference.len(), 2);
+        assert!(symmetric_difference.contains(&1));
+        assert!(symmetric_difference.contains(&3));
+        assert!(!symmetric_difference.contains(&2));
+    }
+
+    #[test]
+    fn test_operators() {
+        let mut local = AWSet::new();
+        local.insert(1);
+        local.insert(2);
+
+        let mut remote = AWSet::new();
+        remote.insert(2);
+        remote.insert(3);
+
+        assert_eq!(&local | &remote, local.union(&remote));
+        assert_eq!(&local & &remote, local.intersection(&remote));
+        assert_eq!(&local ^ &remote, local.symmetric_difference(&remote));
+
+        let diff = &local - &remote;
+        assert_eq!(diff.len(), 1);
+        assert!(diff.contains(&1));
+    }
+
+    #[test]
+    fn test_dots_are_unique_across_replicas() {
+        let mut local = AWSet::with_replica(1);
+        local.insert("a");
+
+        let mut remote = AWSet::with_replica(2);
+        remote.insert("b");
+
+        // Both replicas mint their first dot from the same local counter, but the replica id
+        // keeps them from colliding, so joining never drops either value.
+        local.join(remote.split());
+        assert_eq!(local.len(), 2);
+        assert!(local.contains(&"a"));
+        assert!(local.contains(&"b"));
+    }
+
+    #[test]
+    fn test_fork_reseeds_replica_identity() {
+        let mut common = AWSet::with_replica(1);
+        common.insert("a");
+
+        let mut local = common.clone().fork(2);
+        let mut remote = common.fork(3);
+
+        local.insert("b");
+        remote.insert("c");
+
+        local.join(remote.split());
+        assert_eq!(local.len(), 3);
+        assert!(local.contains(&"a"));
+        assert!(local.contains(&"b"));
+        assert!(local.contains(&"c"));
+    }
+}
+
+/// An [`AWSet`] backed by a persistent [`Hamt`] instead of a plain `HashMap`/`HashSet`.
+///
+/// Mirrors [`GSetHamt`]'s rationale: [`AWSet::split`] allocates one `HashMap`/`HashSet` per
+/// decomposition and [`AWSet::clone`] deep-copies both `inserted` and `removed`, which dominates
+/// `BloomBuckets::sync` on large replicas. Backing both maps with a [`Hamt`] makes `clone` `O(1)`
+/// and `split`/`join` pay only for the spine each element touches, while keeping the
+/// [`Decompose`]/[`Extract`]/[`Measure`] surface identical to [`AWSet`].
+///
+/// Elements are tagged with the same [`Dot`] scheme `AWSet` uses rather than a randomly drawn
+/// `u64`: two independent replicas minting ids off `rand::thread_rng()` can pick the same id for
+/// different values, and `join` would then silently let one clobber the other.
+///
+/// [`AWSet::split`]: Decompose::split
+/// [`AWSet::clone`]: Clone::clone
+#[derive(Clone, Debug)]
+pub struct AWSetHamt<T> {
+    replica_id: u64,
+    counter: u64,
+    inserted: Hamt<Dot, T>,
+    removed: Hamt<Dot, ()>,
+}
+
+impl<T> Default for AWSetHamt<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> AWSetHamt<T> {
+    /// Creates an empty `AWSetHamt` identified by a fresh, randomly chosen replica id.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_replica(rand::thread_rng().gen())
+    }
+
+    /// Creates an empty `AWSetHamt` that mints dots under the given `replica_id`.
+    ///
+    /// Two `AWSetHamt`s must use distinct replica ids if they are meant to evolve independently:
+    /// dots are only guaranteed unique across replicas that don't share one.
+    #[inline]
+    #[must_use]
+    pub fn with_replica(replica_id: u64) -> Self {
+        Self {
+            replica_id,
+            counter: 0,
+            inserted: Hamt::new(),
+            removed: Hamt::new(),
+        }
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !self.inserted.iter().any(|(id, _)| !self.removed.contains_key(id))
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inserted
+            .iter()
+            .filter(|(id, _)| !self.removed.contains_key(*id))
+            .count()
+    }
+
+    /// Re-assigns the replica identity used to mint new dots, leaving the elements already
+    /// observed untouched. See [`AWSet::fork`].
+    #[inline]
+    #[must_use]
+    pub fn fork(mut self, replica_id: u64) -> Self {
+        self.replica_id = replica_id;
+        self.counter = 0;
+        self
+    }
+
+    fn next_dot(&mut self) -> Dot {
+        self.counter += 1;
+        Dot(self.replica_id, self.counter)
+    }
+}
+
+impl<T> AWSetHamt<T>
+where
+    T: Clone + Eq + Hash,
+{
+    #[inline]
+    pub fn contains(&self, value: &T) -> bool {
+        self.inserted
+            .iter()
+            .any(|(id, v)| value == v && !self.removed.contains_key(id))
+    }
+
+    #[inline]
+    pub fn elements(&self) -> Elements<'_, T> {
+        Elements {
+            elems: self
+                .inserted
+                .iter()
+                .filter_map(|(id, v)| (!self.removed.contains_key(id)).then_some(v))
+                .collect(),
+            idx: 0,
+        }
+    }
+
+    pub fn remove(&mut self, value: &T) -> Self {
+        let ids = self
+            .inserted
+            .iter()
+            .filter_map(|(id, v)| (value == v && !self.removed.contains_key(id)).then_some(*id))
+            .collect::<Vec<_>>();
+
+        ids.iter().for_each(|id| {
+            self.removed.insert(*id, ());
+        });
+
+        Self {
+            replica_id: self.replica_id,
+            counter: self.counter,
+            inserted: Hamt::new(),
+            removed: Hamt::from_iter(ids.into_iter().map(|id| (id, ()))),
+        }
+    }
+
+    pub fn insert(&mut self, value: T) -> Self {
+        let id = self.next_dot();
+        self.inserted.insert(id, value.clone());
+
+        Self {
+            replica_id: self.replica_id,
+            counter: self.counter,
+            inserted: Hamt::from_iter([(id, value)]),
+            removed: Hamt::new(),
+        }
+    }
+}
+
+impl<T> Decompose for AWSetHamt<T>
+where
+    T: Clone + Eq + Hash,
+{
+    type Decomposition = AWSetHamt<T>;
+
+    fn split(&self) -> Vec<Self::Decomposition> {
+        let inserted = self.inserted.iter().map(|(id, v)| Self {
+            replica_id: self.replica_id,
+            counter: self.counter,
+            inserted: Hamt::from_iter([(*id, v.clone())]),
+            removed: Hamt::new(),
+        });
+
+        let removed = self.removed.iter().map(|(id, ())| Self {
+            replica_id: self.replica_id,
+            counter: self.counter,
+            inserted: Hamt::new(),
+            removed: Hamt::from_iter([(*id, ())]),
+        });
+
+        inserted.chain(removed).collect()
+    }
+
+    fn join(&mut self, deltas: Vec<Self::Decomposition>) {
+        deltas.into_iter().for_each(|delta| {
+            self.inserted
+                .extend(delta.inserted.iter().map(|(id, v)| (*id, v.clone())));
+            self.removed
+                .extend(delta.removed.iter().map(|(id, ())| (*id, ())));
+        })
+    }
+
+    fn difference(&self, remote: &Self::Decomposition) -> Self::Decomposition {
+        Self {
+            replica_id: self.replica_id,
+            counter: self.counter,
+            inserted: self
+                .inserted
+                .iter()
+                .filter(|(id, _)| !remote.inserted.contains_key(*id))
+                .map(|(id, v)| (*id, v.clone()))
+                .collect(),
+            removed: self
+                .removed
+                .iter()
+                .filter(|(id, ())| !remote.removed.contains_key(*id))
+                .map(|(id, ())| (*id, ()))
+                .collect(),
+        }
+    }
+}
+
+impl<T> Extract for AWSetHamt<T>
+where
+    T: Clone + Eq + Hash,
+{
+    type Item = Either<(Dot, T), Dot>;
+
+    fn extract(&self) -> Self::Item {
+        if self.removed.is_empty() {
+            assert_eq!(
+                self.inserted.len(),
+                1,
+                "a join-decomposition should have a single item"
+            );
+
+            Left(
+                self.inserted
+                    .iter()
+                    .map(|(id, v)| (*id, v.clone()))
+                    .next()
+                    .unwrap(),
+            )
+        } else {
+            assert_eq!(
+                self.removed.len(),
+                1,
+                "a join-decomposition should have a single item"
+            );
+
+            Right(self.removed.iter().map(|(id, ())| *id).next().unwrap())
+        }
+    }
+}
+
+impl<T> Measure for AWSetHamt<T>
+where
+    T: Clone + Eq + Hash + SizeOf,
+{
+    fn len(replica: &Self) -> usize {
+        replica.inserted.len() + replica.removed.len()
+    }
+
+    fn size_of(replica: &Self) -> usize {
+        replica.inserted.len() * mem::size_of::<Dot>()
+            + replica.inserted.iter().map(|(_, v)| SizeOf::size_of(v)).sum::<usize>()
+            + replica.removed.len() * mem::size_of::<Dot>()
+    }
+
+    fn false_matches(&self, other: &Self) -> usize {
+        self.elements().filter(|v| !other.contains(v)).count()
+            + other.elements().filter(|v| !self.contains(v)).count()
+    }
+}
+
+impl<T> PartialEq for AWSetHamt<T>
+where
+    T: Clone + Eq + Hash,
+{
+    fn eq(&self, other: &Self) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+
+        self.inserted
+            .iter()
+            .filter_map(|(id, v)| (!self.removed.contains_key(id)).then_some(v))
+            .all(|v| other.contains(v))
+    }
+}
+
+impl<T> Eq for AWSetHamt<T> where T: Clone + Eq + Hash {}
+
+#[cfg(test)]
+mod awset_hamt {
+    use super::*;
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut awset = AWSetHamt::new();
+        assert_eq!(awset.len(), 0);
+        assert!(awset.is_empty());
+
+        awset.insert(1);
+        awset.insert(2);
+        awset.insert(3);
+        assert_eq!(awset.len(), 3);
+        assert!(!awset.is_empty());
+
+        awset.remove(&2);
+        awset.remove(&2);
+        awset.remove(&4);
+        assert_eq!(awset.len(), 2);
+
+        awset.insert(2);
+        awset.insert(4);
+        assert_eq!(awset.len(), 4);
+    }
+
+    #[test]
+    fn test_split_and_join() {
+        let mut splittable = AWSetHamt::new();
+
+        splittable.insert(1);
+        splittable.insert(2);
+        splittable.insert(3);
+        splittable.remove(&2);
+        splittable.remove(&4);
+
+        assert!(splittable.contains(&1));
+        assert!(splittable.contains(&3));
+
+        let decompositions = splittable.split();
+        assert_eq!(decompositions.len(), 4);
+
+        let mut joinable = AWSetHamt::new();
+        joinable.join(decompositions);
+
+        assert_eq!(splittable, joinable);
+    }
+
+    #[test]
+    fn test_clone_is_cheap_and_independent() {
+        let mut original = AWSetHamt::new();
+        original.insert(1);
+        original.insert(2);
+
+        let snapshot = original.clone();
+        original.insert(3);
+        original.remove(&1);
+
+        assert_eq!(snapshot.len(), 2);
+        assert!(snapshot.contains(&1));
+        assert!(!snapshot.contains(&3));
+    }
+}
+
+/// A counter that supports both increment and decrement, kept eventually consistent as a pair of
+/// grow-only per-replica tallies: one counts increments, the other decrements, and the observed
+/// value is their difference. Joining takes the pointwise maximum of each tally, exactly like a
+/// plain grow-only counter, so the pair converges the same way `GCounter` would.
+#[derive(Clone, Debug, Default)]
+pub struct PNCounter<I> {
+    pos: HashMap<I, u64>,
+    neg: HashMap<I, u64>,
+}
+
+impl<I> PNCounter<I> {
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            pos: HashMap::new(),
+            neg: HashMap::new(),
+        }
+    }
+}
+
+impl<I> PNCounter<I>
+where
+    I: Clone + Eq + Hash,
+{
+    /// Returns the counter's current value, the sum of increments minus the sum of decrements.
+    pub fn count(&self) -> i64 {
+        let pos = self.pos.values().sum::<u64>();
+        let neg = self.neg.values().sum::<u64>();
+
+        i64::try_from(pos).unwrap_or(i64::MAX) - i64::try_from(neg).unwrap_or(i64::MAX)
+    }
+
+    pub fn increment(&mut self, id: &I) -> Self {
+        let count = self
+            .pos
+            .entry(id.clone())
+            .and_modify(|count| *count += 1)
+            .or_insert(1);
+
+        Self {
+            pos: HashMap::from([(id.clone(), *count)]),
+            neg: HashMap::new(),
+        }
+    }
+
+    pub fn decrement(&mut self, id: &I) -> Self {
+        let count = self
+            .neg
+            .entry(id.clone())
+            .and_modify(|count| *count += 1)
+            .or_insert(1);
+
+        Self {
+            pos: HashMap::new(),
+            neg: HashMap::from([(id.clone(), *count)]),
+        }
+    }
+
+    /// Reconciles `self` against an authoritative recount of every replica's net contribution,
+    /// returning only the deltas needed to bring `self` into agreement.
+    ///
+    /// Lost deltas, a partial join, or a crash mid-sync can leave a replica permanently
+    /// under-reporting some id's contribution, with no peer left holding the missing delta to
+    /// re-sync from. `truth` gives each id's authoritative net value as recounted offline; this
+    /// builds the counter that recount implies and folds it through [`Decompose::difference`], so
+    /// the repair comes out as an ordinary delta that flows through the normal sync path instead
+    /// of a special-cased state replacement.
+    pub fn reconcile_from<Src>(&self, truth: Src) -> Vec<<Self as Decompose>::Decomposition>
+    where
+        Src: IntoIterator<Item = (I, i32)>,
+    {
+        let mut recounted = Self::new();
+
+        for (id, value) in truth {
+            if value >= 0 {
+                recounted.pos.insert(id, u64::from(value.unsigned_abs()));
+            } else {
+                recounted.neg.insert(id, u64::from(value.unsigned_abs()));
+            }
+        }
+
+        recounted.difference(self).split()
+    }
+}
+
+impl<I> Decompose for PNCounter<I>
+where
+    I: Clone + Eq + Hash,
+{
+    type Decomposition = PNCounter<I>;
+
+    fn split(&self) -> Vec<Self::Decomposition> {
+        let pos = self.pos.iter().map(|(id, count)| Self {
+            pos: HashMap::from([(id.clone(), *count)]),
+            neg: HashMap::new(),
+        });
+
+        let neg = self.neg.iter().map(|(id, count)| Self {
+            pos: HashMap::new(),
+            neg: HashMap::from([(id.clone(), *count)]),
+        });
+
+        pos.chain(neg).collect()
+    }
+
+    fn join(&mut self, deltas: Vec<Self::Decomposition>) {
+        for delta in deltas {
+            for (id, count) in delta.pos {
+                self.pos
+                    .entry(id)
+                    .and_modify(|v| *v = (*v).max(count))
+                    .or_insert(count);
+            }
+
+            for (id, count) in delta.neg {
+                self.neg
+                    .entry(id)
+                    .and_modify(|v| *v = (*v).max(count))
+                    .or_insert(count);
+            }
+        }
+    }
+
+    fn difference(&self, remote: &Self::Decomposition) -> Self::Decomposition {
+        let pos = self
+            .pos
+            .iter()
+            .filter(|(id, count)| remote.pos.get(*id).is_none_or(|v| *count > v))
+            .map(|(id, count)| (id.clone(), *count))
+            .collect();
+
+        let neg = self
+            .neg
+            .iter()
+            .filter(|(id, count)| remote.neg.get(*id).is_none_or(|v| *count > v))
+            .map(|(id, count)| (id.clone(), *count))
+            .collect();
+
+        Self { pos, neg }
+    }
+}
+
+impl<I> Extract for PNCounter<I>
+where
+    I: Clone + Eq + Hash,
+{
+    type Item = Either<(I, u64), (I, u64)>;
+
+    fn extract(&self) -> Self::Item {
+        if self.neg.is_empty() {
+            assert_eq!(
+                self.pos.len(),
+                1,
+                "a join-decomposition should have a single item"
+            );
+
+            Left(self.pos.iter().map(|(id, count)| (id.clone(), *count)).next().unwrap())
+        } else {
+            assert_eq!(
+                self.neg.len(),
+                1,
+                "a join-decomposition should have a single item"
+            );
+
+            Right(self.neg.iter().map(|(id, count)| (id.clone(), *count)).next().unwrap())
+        }
+    }
+}
+
+impl<I> Measure for PNCounter<I>
+where
+    I: Clone + Eq + Hash + SizeOf,
+{
+    fn len(replica: &Self) -> usize {
+        replica.pos.len() + replica.neg.len()
+    }
+
+    fn size_of(replica: &Self) -> usize {
+        replica
+            .pos
+            .keys()
+            .chain(replica.neg.keys())
+            .map(|id| id.size_of() + mem::size_of::<u64>())
+            .sum()
+    }
+
+    fn false_matches(&self, other: &Self) -> usize {
+        usize::from(self.count() != other.count())
+    }
+}
+
+impl<I> PartialEq for PNCounter<I>
+where
+    I: Eq + Hash,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.pos == other.pos && self.neg == other.neg
+    }
+}
+
+impl<I> Eq for PNCounter<I> where I: Eq + Hash {}
+
+#[cfg(test)]
+mod pncounter {
+    use super::*;
+
+    #[test]
+    fn test_increment_and_decrement() {
+        let mut counter = PNCounter::new();
+
+        counter.increment(&1);
+        counter.increment(&2);
+        counter.decrement(&1);
+        counter.increment(&1);
+
+        assert_eq!(counter.count(), 2);
+    }
+
+    #[test]
+    fn test_split_and_join() {
+        let mut splittable = PNCounter::new();
+        splittable.increment(&1);
+        splittable.increment(&2);
+        splittable.decrement(&1);
+
+        let decompositions = splittable.split();
+        assert_eq!(decompositions.len(), 3);
+
+        let mut joinable = PNCounter::new();
+        joinable.join(decompositions);
+
+        assert_eq!(splittable, joinable);
+        assert_eq!(joinable.count(), 1);
+    }
+
+    #[test]
+    fn test_difference() {
+        let local = PNCounter {
+            pos: HashMap::from([(1, 3), (2, 1)]),
+            neg: HashMap::from([(1, 1)]),
+        };
+
+        let remote = PNCounter {
+            pos: HashMap::from([(1, 2)]),
+            neg: HashMap::from([(1, 1)]),
+        };
+
+        let diff = local.difference(&remote);
+        assert_eq!(diff.pos, HashMap::from([(1, 3), (2, 1)]));
+        assert!(diff.neg.is_empty());
+    }
+
+    #[test]
+    fn test_difference_synced() {
+        let local = PNCounter {
+            pos: HashMap::from([(1, 3)]),
+            neg: HashMap::from([(1, 1)]),
+        };
+        let remote = local.clone();
+
+        let diff = local.difference(&remote);
+        assert!(diff.pos.is_empty());
+        assert!(diff.neg.is_empty());
+    }
+
+    #[test]
+    fn test_reconcile_from_recovers_undercounted_ids() {
+        let mut counter = PNCounter::new();
+        counter.increment(&1);
+        counter.decrement(&2);
+        assert_eq!(counter.count(), 0);
+
+        // A lost delta left replica 1's true count of 3 unrecorded, and replica 3 was never
+        // observed at all.
+        let deltas = counter.reconcile_from([(1, 3), (2, -1), (3, 2)]);
+
+        let mut repaired = counter.clone();
+        repaired.join(deltas);
+
+        assert_eq!(repaired.pos, HashMap::from([(1, 3), (3, 2)]));
+        assert_eq!(repaired.neg, HashMap::from([(2, 1)]));
+        assert_eq!(repaired.count(), 4);
+    }
+
+    #[test]
+    fn test_reconcile_from_already_synced() {
+        let mut counter = PNCounter::new();
+        counter.increment(&1);
+        counter.decrement(&2);
+
+        assert!(counter.reconcile_from([(1, 1), (2, -1)]).is_empty());
+    }
+}
+
+/// A last-writer-wins register: the most recently set value wins. Ties between two replicas
+/// writing under the same logical `timestamp` are broken deterministically by the minting
+/// [`Dot`], so every replica picks the same winner without further coordination.
+///
+/// This reuses the [`Dot`] already defined in this module as the tiebreak rather than pulling in
+/// the generic `causal` clock from the standalone `crdt` crate (`crates/crdt`): that crate evolves
+/// independently of this binary and isn't one of its dependencies, whereas `Dot` already gives the
+/// same per-replica-monotonic-counter guarantee `AWSet` relies on.
+#[derive(Clone, Debug)]
+pub struct LWWRegister<T> {
+    replica_id: u64,
+    counter: u64,
+    value: Option<(u64, Dot, T)>,
+}
+
+impl<T> LWWRegister<T> {
+    /// Creates an empty register identified by a fresh, randomly chosen replica id.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_replica(rand::thread_rng().gen())
+    }
+
+    /// Creates an empty register that mints dots under the given `replica_id`.
+    #[inline]
+    #[must_use]
+    pub fn with_replica(replica_id: u64) -> Self {
+        Self {
+            replica_id,
+            counter: 0,
+            value: None,
+        }
+    }
+
+    /// Re-assigns the replica identity used to mint new dots. See [`AWSet::fork`].
+    #[inline]
+    #[must_use]
+    pub fn fork(mut self, replica_id: u64) -> Self {
+        self.replica_id = replica_id;
+        self.counter = 0;
+        self
+    }
+
+    fn next_dot(&mut self) -> Dot {
+        self.counter += 1;
+        Dot(self.replica_id, self.counter)
+    }
+
+    /// Returns the current value, or `None` if nothing has ever been set.
+    #[inline]
+    pub fn get(&self) -> Option<&T> {
+        self.value.as_ref().map(|(_, _, value)| value)
+    }
+}
+
+impl<T> Default for LWWRegister<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> LWWRegister<T>
+where
+    T: Clone,
+{
+    /// Sets the register to `value` under the given logical `timestamp`, returning the delta
+    /// to propagate. The write only takes effect locally if it is not dominated by the current
+    /// value under the `(timestamp, dot)` tiebreak.
+    pub fn set(&mut self, timestamp: u64, value: T) -> Self {
+        let dot = self.next_dot();
+
+        if self
+            .value
+            .as_ref()
+            .is_none_or(|(ts, id, _)| (timestamp, dot) > (*ts, *id))
+        {
+            self.value = Some((timestamp, dot, value.clone()));
+        }
+
+        Self {
+            replica_id: self.replica_id,
+            counter: self.counter,
+            value: Some((timestamp, dot, value)),
+        }
+    }
+}
+
+impl<T> Decompose for LWWRegister<T>
+where
+    T: Clone,
+{
+    type Decomposition = LWWRegister<T>;
+
+    fn split(&self) -> Vec<Self::Decomposition> {
+        match &self.value {
+            Some(_) => vec![self.clone()],
+            None => vec![],
+        }
+    }
+
+    fn join(&mut self, deltas: Vec<Self::Decomposition>) {
+        for delta in deltas {
+            let Some((timestamp, dot, value)) = delta.value else {
+                continue;
+            };
+
+            if self
+                .value
+                .as_ref()
+                .is_none_or(|(ts, id, _)| (timestamp, dot) > (*ts, *id))
+            {
+                self.value = Some((timestamp, dot, value));
+            }
+        }
+    }
+
+    fn difference(&self, remote: &Self::Decomposition) -> Self::Decomposition {
+        let value = match (&self.value, &remote.value) {
+            (Some((ts, dot, value)), Some((rts, rdot, _))) if (*ts, *dot) > (*rts, *rdot) => {
+                Some((*ts, *dot, value.clone()))
+            }
+            (Some((ts, dot, value)), None) => Some((*ts, *dot, value.clone())),
+            _ => None,
+        };
+
+        Self {
+            replica_id: self.replica_id,
+            counter: self.counter,
+            value,
+        }
+    }
+}
+
+impl<T> Extract for LWWRegister<T>
+where
+    T: Clone + Hash,
+{
+    type Item = T;
+
+    fn extract(&self) -> Self::Item {
+        self.value
+            .as_ref()
+            .map(|(_, _, value)| value.clone())
+            .expect("a join-decomposition should have a value")
+    }
+}
+
+impl<T> Measure for LWWRegister<T>
+where
+    T: Clone + PartialEq + SizeOf,
+{
+    fn len(replica: &Self) -> usize {
+        usize::from(replica.value.is_some())
+    }
+
+    fn size_of(replica: &Self) -> usize {
+        replica
+            .value
+            .as_ref()
+            .map(|(_, _, value)| mem::size_of::<u64>() + mem::size_of::<Dot>() + value.size_of())
+            .unwrap_or(0)
+    }
+
+    fn false_matches(&self, other: &Self) -> usize {
+        usize::from(self.get() != other.get())
+    }
+}
+
+impl<T> PartialEq for LWWRegister<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+
+impl<T> Eq for LWWRegister<T> where T: Eq {}
+
+#[cfg(test)]
+mod lwwregister {
+    use super::*;
+
+    #[test]
+    fn test_set_keeps_latest_timestamp() {
+        let mut register = LWWRegister::new();
+        register.set(1, "a");
+        register.set(3, "c");
+        register.set(2, "b");
+
+        assert_eq!(register.get(), Some(&"c"));
+    }
+
+    #[test]
+    fn test_concurrent_writes_break_ties_by_dot() {
+        let mut local = LWWRegister::with_replica(1);
+        let mut remote = LWWRegister::with_replica(2);
+
+        let local_delta = local.set(1, "a");
+        let remote_delta = remote.set(1, "b");
+
+        local.join(vec![remote_delta]);
+        remote.join(vec![local_delta]);
+
+        assert_eq!(local, remote);
+    }
+
+    #[test]
+    fn test_split_and_join() {
+        let mut splittable = LWWRegister::new();
+        splittable.set(1, "a".to_string());
+
+        let decompositions = splittable.split();
+        assert_eq!(decompositions.len(), 1);
+
+        let mut joinable = LWWRegister::new();
+        joinable.join(decompositions);
+
+        assert_eq!(splittable, joinable);
+    }
+
+    #[test]
+    fn test_difference() {
+        let mut local = LWWRegister::with_replica(1);
+        local.set(5, "a");
+
+        let remote = LWWRegister::with_replica(2);
+
+        let diff = local.difference(&remote);
+        assert_eq!(diff.get(), Some(&"a"));
+    }
+
+    #[test]
+    fn test_difference_synced() {
+        let mut local = LWWRegister::with_replica(1);
+        local.set(5, "a");
+        let remote = local.clone();
+
+        let diff = local.difference(&remote);
+        assert_eq!(diff.get(), None);
+    }
+}
+
+/// An add-wins, observed-remove map whose values are themselves [`Decompose`] CRDTs.
+///
+/// Mirrors [`AWSet`]'s dot-based discipline at the key level: every `insert` mints a fresh [`Dot`]
+/// naming that particular instance of a key, so a concurrent `insert`/`remove` always resolves in
+/// favor of the insert (a remove can only tombstone dots it has already observed). Unlike
+/// `AWSet`, the payload behind a live dot isn't replaced wholesale on every join — it is merged
+/// through `V`'s own [`Decompose::join`], so e.g. an `ORMap<String, GSet<String>>` accumulates
+/// per-element deltas under each key instead of clobbering the whole value.
+#[derive(Clone, Debug)]
+pub struct ORMap<K, V> {
+    replica_id: u64,
+    counter: u64,
+    entries: HashMap<K, HashMap<Dot, V>>,
+    removed: HashSet<Dot>,
+}
+
+impl<K, V> ORMap<K, V> {
+    /// Creates an empty `ORMap` identified by a fresh, randomly chosen replica id.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_replica(rand::thread_rng().gen())
+    }
+
+    /// Creates an empty `ORMap` that mints dots under the given `replica_id`. See
+    /// [`AWSet::with_replica`].
+    #[inline]
+    #[must_use]
+    pub fn with_replica(replica_id: u64) -> Self {
+        Self {
+            replica_id,
+            counter: 0,
+            entries: HashMap::new(),
+            removed: HashSet::new(),
+        }
+    }
+
+    /// Re-assigns the replica identity used to mint new dots. See [`AWSet::fork`].
+    #[inline]
+    #[must_use]
+    pub fn fork(mut self, replica_id: u64) -> Self {
+        self.replica_id = replica_id;
+        self.counter = 0;
+        self
+    }
+
+    fn next_dot(&mut self) -> Dot {
+        self.counter += 1;
+        Dot(self.replica_id, self.counter)
+    }
+}
+
+impl<K, V> Default for ORMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> ORMap<K, V>
+where
+    K: Eq + Hash,
+{
+    fn live_dots(&self, key: &K) -> impl Iterator<Item = Dot> + '_ {
+        self.entries
+            .get(key)
+            .into_iter()
+            .flat_map(|dots| dots.keys().copied())
+            .filter(|dot| !self.removed.contains(dot))
+    }
+
+    #[inline]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.live_dots(key).next().is_some()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.keys().all(|key| !self.contains_key(key))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.keys().filter(|key| self.contains_key(key)).count()
+    }
+}
+
+impl<K, V> ORMap<K, V>
+where
+    K: Clone + Eq + Hash,
+    V: Clone + Decompose<Decomposition = V> + Default,
+{
+    /// Returns the value observed under `key`, merging together every instance still live (i.e.
+    /// every dot a concurrent remove hasn't tombstoned) through `V`'s own join.
+    pub fn get(&self, key: &K) -> Option<V> {
+        let dots = self.entries.get(key)?;
+        let mut merged = V::default();
+        let mut any = false;
+
+        for (dot, value) in dots {
+            if !self.removed.contains(dot) {
+                merged.join(vec![value.clone()]);
+                any = true;
+            }
+        }
+
+        any.then_some(merged)
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Self {
+        let dot = self.next_dot();
+        self.entries
+            .entry(key.clone())
+            .or_default()
+            .insert(dot, value.clone());
+
+        Self {
+            replica_id: self.replica_id,
+            counter: self.counter,
+            entries: HashMap::from([(key, HashMap::from([(dot, value)]))]),
+            removed: HashSet::new(),
+        }
+    }
+
+    /// Applies `f` to the single live instance of `key`'s value, returning the delta it produces.
+    ///
+    /// Panics if `key` doesn't have exactly one live instance: a concurrent remove/insert must be
+    /// resolved (by re-inserting) before the value can be updated unambiguously.
+    pub fn update<F>(&mut self, key: &K, f: F) -> Self
+    where
+        F: FnOnce(&mut V) -> V,
+    {
+        let live = self.live_dots(key).collect::<Vec<_>>();
+        assert_eq!(
+            live.len(),
+            1,
+            "update requires a single live instance of the key"
+        );
+        let dot = live[0];
+
+        let value = self
+            .entries
+            .get_mut(key)
+            .and_then(|dots| dots.get_mut(&dot))
+            .expect("live dot should have a value");
+        let delta = f(value);
+
+        Self {
+            replica_id: self.replica_id,
+            counter: self.counter,
+            entries: HashMap::from([(key.clone(), HashMap::from([(dot, delta)]))]),
+            removed: HashSet::new(),
+        }
+    }
+
+    pub fn remove(&mut self, key: &K) -> Self {
+        let live = self.live_dots(key).collect::<HashSet<_>>();
+        self.removed.extend(live.iter().copied());
+
+        Self {
+            replica_id: self.replica_id,
+            counter: self.counter,
+            entries: HashMap::new(),
+            removed: live,
+        }
+    }
+}
+
+impl<K, V> Decompose for ORMap<K, V>
+where
+    K: Clone + Eq + Hash,
+    V: Clone + Decompose<Decomposition = V> + Default,
+{
+    type Decomposition = ORMap<K, V>;
+
+    fn split(&self) -> Vec<Self::Decomposition> {
+        let entries = self.entries.iter().flat_map(|(key, dots)| {
+            dots.iter().flat_map(move |(dot, value)| {
+                value.split().into_iter().map(move |delta| Self {
+                    replica_id: self.replica_id,
+                    counter: self.counter,
+                    entries: HashMap::from([(key.clone(), HashMap::from([(*dot, delta)]))]),
+                    removed: HashSet::new(),
+                })
+            })
+        });
+
+        let removed = self.removed.iter().copied().map(|dot| Self {
+            replica_id: self.replica_id,
+            counter: self.counter,
+            entries: HashMap::new(),
+            removed: HashSet::from([dot]),
+        });
+
+        entries.chain(removed).collect()
+    }
+
+    fn join(&mut self, deltas: Vec<Self::Decomposition>) {
+        for delta in deltas {
+            for (key, dots) in delta.entries {
+                let slot = self.entries.entry(key).or_default();
+
+                for (dot, value) in dots {
+                    slot.entry(dot).or_default().join(vec![value]);
+                }
+            }
+
+            self.removed.extend(delta.removed);
+        }
+    }
+
+    fn difference(&self, remote: &Self::Decomposition) -> Self::Decomposition {
+        let mut entries = HashMap::new();
+
+        for (key, dots) in &self.entries {
+            let diff = dots
+                .iter()
+                .map(|(dot, value)| {
+                    let delta = match remote.entries.get(key).and_then(|m| m.get(dot)) {
+                        Some(remote_value) => value.difference(remote_value),
+                        None => value.clone(),
+                    };
+
+                    (*dot, delta)
+                })
+                .collect::<HashMap<_, _>>();
+
+            if !diff.is_empty() {
+                entries.insert(key.clone(), diff);
+            }
+        }
+
+        Self {
+            replica_id: self.replica_id,
+            counter: self.counter,
+            entries,
+            removed: self.removed.difference(&remote.removed).copied().collect(),
         }
     }
 }
 
-impl<T> Extract for AWSet<T>
+impl<K, V> Extract for ORMap<K, V>
 where
-    T: Clone + Eq + Hash,
+    K: Clone + Eq + Hash,
+    V: Clone + Decompose<Decomposition = V> + Extract,
 {
-    type Item = Either<(u64, T), u64>;
+    type Item = Either<(K, Dot, V::Item), Dot>;
 
     fn extract(&self) -> Self::Item {
         if self.removed.is_empty() {
             assert_eq!(
-                self.inserted.len(),
+                self.entries.len(),
+                1,
+                "a join-decomposition should have a single key"
+            );
+
+            let (key, dots) = self.entries.iter().next().unwrap();
+            assert_eq!(
+                dots.len(),
                 1,
                 "a join-decomposition should have a single item"
             );
 
-            Left(
-                self.inserted
-                    .iter()
-                    .map(|(id, v)| (*id, v.clone()))
-                    .next()
-                    .unwrap(),
-            )
+            let (dot, value) = dots.iter().next().unwrap();
+            Left((key.clone(), *dot, value.extract()))
         } else {
             assert_eq!(
                 self.removed.len(),
@@ -402,174 +2506,404 @@ where
                 "a join-decomposition should have a single item"
             );
 
-            Right(self.removed.iter().cloned().next().unwrap())
+            Right(self.removed.iter().copied().next().unwrap())
         }
     }
 }
 
-impl Measure for AWSet<String> {
+impl<K, V> Measure for ORMap<K, V>
+where
+    K: Clone + Eq + Hash + SizeOf,
+    V: Clone + Decompose<Decomposition = V> + Default + PartialEq + Measure,
+{
     fn len(replica: &Self) -> usize {
-        replica.inserted.len() + replica.removed.len()
+        replica.entries.values().map(HashMap::len).sum::<usize>() + replica.removed.len()
     }
 
     fn size_of(replica: &Self) -> usize {
-        replica.inserted.len() * mem::size_of::<u64>()
-            + replica.inserted.values().map(String::len).sum::<usize>()
-            + replica.removed.len() * mem::size_of::<u64>()
+        replica
+            .entries
+            .iter()
+            .map(|(key, dots)| {
+                dots.values()
+                    .map(|value| mem::size_of::<Dot>() + key.size_of() + V::size_of(value))
+                    .sum::<usize>()
+            })
+            .sum::<usize>()
+            + replica.removed.len() * mem::size_of::<Dot>()
     }
 
     fn false_matches(&self, other: &Self) -> usize {
-        self.elements().filter(|v| !other.contains(v)).count()
-            + other.elements().filter(|v| !self.contains(v)).count()
+        self.entries
+            .keys()
+            .chain(other.entries.keys())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .filter(|key| self.get(key) != other.get(key))
+            .count()
     }
 }
 
-impl<T> PartialEq for AWSet<T>
+impl<K, V> PartialEq for ORMap<K, V>
 where
-    T: Eq + Hash,
+    K: Clone + Eq + Hash,
+    V: Clone + Decompose<Decomposition = V> + Default + PartialEq,
 {
     fn eq(&self, other: &Self) -> bool {
         if self.len() != other.len() {
             return false;
         }
 
-        self.inserted
-            .iter()
-            .filter_map(|(id, v)| (!self.removed.contains(id)).then_some(v))
-            .all(|id| other.contains(id))
+        self.entries
+            .keys()
+            .filter(|key| self.contains_key(key))
+            .all(|key| self.get(key) == other.get(key))
     }
 }
 
-impl<T> Eq for AWSet<T> where T: Eq + Hash {}
+impl<K, V> Eq for ORMap<K, V>
+where
+    K: Clone + Eq + Hash,
+    V: Clone + Decompose<Decomposition = V> + Default + Eq,
+{
+}
 
 #[cfg(test)]
-mod awset {
+mod ormap {
     use super::*;
 
     #[test]
-    fn test_insert_and_remove() {
-        let mut awset = AWSet::new();
-        assert_eq!(awset.len(), 0);
-        assert!(awset.is_empty());
+    fn test_insert_and_get() {
+        let mut map = ORMap::<String, GSet<String>>::new();
+        map.insert("a".to_string(), GSet::new());
+        map.update(&"a".to_string(), |gset| gset.insert("x".to_string()));
+
+        assert!(map.contains_key(&"a".to_string()));
+        assert_eq!(map.get(&"a".to_string()).unwrap().len(), 1);
+        assert!(!map.contains_key(&"b".to_string()));
+    }
 
-        awset.insert(1);
-        awset.insert(2);
-        awset.insert(3);
-        assert_eq!(awset.len(), 3);
-        assert!(!awset.is_empty());
+    #[test]
+    fn test_remove() {
+        let mut map = ORMap::<String, GSet<String>>::new();
+        map.insert("a".to_string(), GSet::new());
+        map.remove(&"a".to_string());
 
-        awset.remove(&2);
-        awset.remove(&2);
-        awset.remove(&4);
-        assert_eq!(awset.len(), 2);
+        assert!(!map.contains_key(&"a".to_string()));
+        assert_eq!(map.get(&"a".to_string()), None);
+    }
 
-        awset.insert(2);
-        awset.insert(4);
-        assert_eq!(awset.len(), 4);
+    #[test]
+    fn test_split_and_join() {
+        let mut splittable = ORMap::<String, GSet<String>>::new();
+        splittable.insert("a".to_string(), GSet::new());
+        splittable.update(&"a".to_string(), |gset| gset.insert("x".to_string()));
+        splittable.insert("b".to_string(), GSet::new());
+        splittable.remove(&"b".to_string());
+
+        let decompositions = splittable.split();
+
+        let mut joinable = ORMap::<String, GSet<String>>::new();
+        joinable.join(decompositions);
+
+        assert_eq!(splittable, joinable);
     }
 
     #[test]
-    fn test_elements() {
-        let mut awset = AWSet::new();
-        awset.insert(1);
-        awset.insert(2);
-        awset.insert(3);
+    fn test_concurrent_insert_wins_over_remove() {
+        let mut local = ORMap::<String, GSet<String>>::with_replica(1);
+        let mut remote = ORMap::<String, GSet<String>>::with_replica(2);
 
-        assert!(awset.elements().all(|v| vec![1, 2, 3].contains(v)));
+        let first = local.insert("a".to_string(), GSet::new());
+        remote.join(vec![first]);
 
-        awset.remove(&1);
-        awset.insert(3);
-        awset.remove(&3);
+        // Concurrently: remote removes the key it has observed, while local inserts a brand new
+        // instance of it under a dot the remove could never have seen.
+        let removed = remote.remove(&"a".to_string());
+        let second = local.insert("a".to_string(), GSet::new());
 
-        assert_eq!(awset.elements().next(), Some(&2));
+        local.join(vec![removed]);
+        remote.join(vec![second]);
 
-        awset.remove(&2);
-        assert_eq!(awset.elements().next(), None);
+        assert_eq!(local, remote);
+        assert!(local.contains_key(&"a".to_string()));
     }
 
     #[test]
-    fn test_split_and_join() {
-        let mut splittable = AWSet::new();
+    fn test_difference() {
+        let mut local = ORMap::<String, GSet<String>>::with_replica(1);
+        local.insert("a".to_string(), GSet::new());
+        local.update(&"a".to_string(), |gset| gset.insert("x".to_string()));
 
-        splittable.insert(1);
-        splittable.insert(2);
-        splittable.insert(3);
-        splittable.remove(&2);
-        splittable.remove(&4);
+        let remote = ORMap::<String, GSet<String>>::with_replica(2);
 
-        assert!(splittable.contains(&1));
-        assert!(splittable.contains(&3));
+        let diff = local.difference(&remote);
+        assert_eq!(diff.get(&"a".to_string()).unwrap().len(), 1);
+    }
+}
 
-        let decompositions = splittable.split();
-        assert_eq!(decompositions.len(), 4);
+/// Several named grow-only counters sharing one [`Decompose`]/[`Extract`] surface, so one logical
+/// entity can track more than one tally (e.g. objects and bytes per bucket) and still synchronize
+/// in a single pass instead of one sync round per counter.
+///
+/// Mirrors [`PNCounter`]'s flat `HashMap<I, u64>` tally, just keyed by an extra `name` level: each
+/// name gets its own tally, converging the same way a lone grow-only counter would.
+#[derive(Clone, Debug, Default)]
+pub struct MultiCounter<I> {
+    counters: HashMap<String, HashMap<I, u64>>,
+}
 
-        let mut joinable = AWSet::new();
-        joinable.join(decompositions);
+impl<I> MultiCounter<I> {
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            counters: HashMap::new(),
+        }
+    }
+}
 
-        assert_eq!(splittable, joinable);
+impl<I> MultiCounter<I>
+where
+    I: Clone + Eq + Hash,
+{
+    /// Returns the current value of the counter named `name`, or 0 if it has never been
+    /// incremented.
+    pub fn count(&self, name: &str) -> u64 {
+        self.counters
+            .get(name)
+            .map(|tally| tally.values().sum())
+            .unwrap_or(0)
+    }
+
+    /// Returns every named counter's current value.
+    pub fn totals(&self) -> HashMap<String, i32> {
+        self.counters
+            .iter()
+            .map(|(name, tally)| {
+                let total = tally.values().sum::<u64>();
+                (name.clone(), i32::try_from(total).unwrap_or(i32::MAX))
+            })
+            .collect()
+    }
+
+    pub fn increment(&mut self, name: &str, id: &I) -> Self {
+        let count = self
+            .counters
+            .entry(name.to_string())
+            .or_default()
+            .entry(id.clone())
+            .and_modify(|count| *count += 1)
+            .or_insert(1);
+
+        Self {
+            counters: HashMap::from([(name.to_string(), HashMap::from([(id.clone(), *count)]))]),
+        }
+    }
+}
+
+impl<I> Decompose for MultiCounter<I>
+where
+    I: Clone + Eq + Hash,
+{
+    type Decomposition = MultiCounter<I>;
+
+    fn split(&self) -> Vec<Self::Decomposition> {
+        self.counters
+            .iter()
+            .flat_map(|(name, tally)| {
+                tally.iter().map(move |(id, count)| Self {
+                    counters: HashMap::from([(
+                        name.clone(),
+                        HashMap::from([(id.clone(), *count)]),
+                    )]),
+                })
+            })
+            .collect()
+    }
+
+    fn join(&mut self, deltas: Vec<Self::Decomposition>) {
+        for delta in deltas {
+            for (name, tally) in delta.counters {
+                let slot = self.counters.entry(name).or_default();
+
+                for (id, count) in tally {
+                    slot.entry(id)
+                        .and_modify(|v| *v = (*v).max(count))
+                        .or_insert(count);
+                }
+            }
+        }
+    }
+
+    fn difference(&self, remote: &Self::Decomposition) -> Self::Decomposition {
+        let counters = self
+            .counters
+            .iter()
+            .filter_map(|(name, tally)| {
+                let diff = tally
+                    .iter()
+                    .filter(|(id, count)| {
+                        remote
+                            .counters
+                            .get(name)
+                            .and_then(|remote_tally| remote_tally.get(*id))
+                            .is_none_or(|v| *count > v)
+                    })
+                    .map(|(id, count)| (id.clone(), *count))
+                    .collect::<HashMap<_, _>>();
+
+                (!diff.is_empty()).then(|| (name.clone(), diff))
+            })
+            .collect();
+
+        Self { counters }
+    }
+}
+
+impl<I> Extract for MultiCounter<I>
+where
+    I: Clone + Eq + Hash,
+{
+    type Item = (String, I);
+
+    fn extract(&self) -> Self::Item {
+        assert_eq!(
+            self.counters.len(),
+            1,
+            "a join-decomposition should have a single name"
+        );
+
+        let (name, tally) = self.counters.iter().next().unwrap();
+        assert_eq!(
+            tally.len(),
+            1,
+            "a join-decomposition should have a single item"
+        );
+
+        let (id, _) = tally.iter().next().unwrap();
+        (name.clone(), id.clone())
+    }
+}
+
+impl<I> Measure for MultiCounter<I>
+where
+    I: Clone + Eq + Hash + SizeOf,
+{
+    fn len(replica: &Self) -> usize {
+        replica.counters.values().map(HashMap::len).sum()
+    }
+
+    fn size_of(replica: &Self) -> usize {
+        replica
+            .counters
+            .iter()
+            .map(|(name, tally)| {
+                tally
+                    .keys()
+                    .map(|id| name.size_of() + id.size_of() + mem::size_of::<u64>())
+                    .sum::<usize>()
+            })
+            .sum()
+    }
+
+    fn false_matches(&self, other: &Self) -> usize {
+        self.counters
+            .keys()
+            .chain(other.counters.keys())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .filter(|name| self.count(name) != other.count(name))
+            .count()
+    }
+}
+
+impl<I> PartialEq for MultiCounter<I>
+where
+    I: Eq + Hash,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.counters == other.counters
     }
+}
+
+impl<I> Eq for MultiCounter<I> where I: Eq + Hash {}
+
+#[cfg(test)]
+mod multicounter {
+    use super::*;
 
     #[test]
-    fn test_difference() {
-        let local = AWSet {
-            inserted: HashMap::from([(1, 1), (2, 3), (3, 2), (4, 4), (5, 10)]),
-            removed: HashSet::from([1, 3]),
-        };
+    fn test_increment_and_count() {
+        let mut counter = MultiCounter::new();
 
-        let remote = AWSet {
-            inserted: HashMap::from([(1, 1), (2, 3), (3, 2)]),
-            removed: HashSet::from([1, 2]),
-        };
+        counter.increment("objects", &1);
+        counter.increment("bytes", &1);
+        counter.increment("objects", &2);
+        counter.increment("objects", &1);
 
-        let diff = local.difference(&remote);
-        assert_eq!(diff.inserted, HashMap::from([(4, 4), (5, 10)]));
-        assert_eq!(diff.removed, HashSet::from([3]));
+        assert_eq!(counter.count("objects"), 3);
+        assert_eq!(counter.count("bytes"), 1);
+        assert_eq!(counter.count("missing"), 0);
     }
 
     #[test]
-    fn test_difference_synced() {
-        let local = AWSet {
-            inserted: HashMap::from([(1, 1), (2, 3), (3, 2), (4, 4), (5, 10)]),
-            removed: HashSet::from([1, 3]),
-        };
+    fn test_totals() {
+        let mut counter = MultiCounter::new();
+        counter.increment("objects", &1);
+        counter.increment("bytes", &1);
+        counter.increment("bytes", &1);
 
-        let remote = AWSet {
-            inserted: HashMap::from([(1, 1), (2, 3), (3, 2), (4, 4), (5, 10)]),
-            removed: HashSet::from([1, 3]),
-        };
+        assert_eq!(
+            counter.totals(),
+            HashMap::from([("objects".to_string(), 1), ("bytes".to_string(), 2)])
+        );
+    }
 
-        assert_eq!(local, remote);
+    #[test]
+    fn test_split_and_join() {
+        let mut splittable = MultiCounter::new();
+        splittable.increment("objects", &1);
+        splittable.increment("objects", &2);
+        splittable.increment("bytes", &1);
 
-        let diff = local.difference(&remote);
-        assert!(diff.inserted.is_empty());
-        assert!(diff.removed.is_empty());
+        let decompositions = splittable.split();
+        assert_eq!(decompositions.len(), 3);
+
+        let mut joinable = MultiCounter::new();
+        joinable.join(decompositions);
+
+        assert_eq!(splittable, joinable);
     }
 
     #[test]
-    fn test_false_matches() {
-        let local = AWSet {
-            inserted: HashMap::from([
-                (1, "1".to_string()),
-                (4, "4".to_string()),
-                (5, "10".to_string()),
+    fn test_difference() {
+        let local = MultiCounter {
+            counters: HashMap::from([
+                ("objects".to_string(), HashMap::from([(1, 3), (2, 1)])),
+                ("bytes".to_string(), HashMap::from([(1, 5)])),
             ]),
-            removed: HashSet::from([1, 4]),
         };
 
-        let remote = AWSet {
-            inserted: HashMap::from([
-                (1, "1".to_string()),
-                (2, "3".to_string()),
-                (3, "2".to_string()),
-            ]),
-            removed: HashSet::from([1, 2]),
+        let remote = MultiCounter {
+            counters: HashMap::from([("objects".to_string(), HashMap::from([(1, 2)]))]),
         };
 
-        let local_elems = local.elements().collect::<HashSet<_>>();
-        let remote_elems = remote.elements().collect::<HashSet<_>>();
+        let diff = local.difference(&remote);
         assert_eq!(
-            local.false_matches(&remote),
-            local_elems.symmetric_difference(&remote_elems).count()
-        )
+            diff.counters,
+            HashMap::from([
+                ("objects".to_string(), HashMap::from([(1, 3), (2, 1)])),
+                ("bytes".to_string(), HashMap::from([(1, 5)])),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_extract() {
+        let mut counter = MultiCounter::new();
+        let delta = counter.increment("objects", &1);
+
+        assert_eq!(delta.extract(), ("objects".to_string(), 1));
     }
 }
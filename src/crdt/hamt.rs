@@ -0,0 +1,437 @@
+use std::{
+    borrow::Borrow,
+    fmt::{self, Debug},
+    hash::{BuildHasher, Hash, RandomState},
+    rc::Rc,
+};
+
+/// Number of bits of the hash consumed per level, i.e. `log2` of the branching factor.
+const BITS: u32 = 5;
+
+/// Number of slots a [`Branch`] can hold, `2^BITS`.
+const ARITY: usize = 1 << BITS;
+
+/// Mask selecting the low `BITS` bits of a shifted hash.
+const MASK: u64 = (ARITY - 1) as u64;
+
+/// Number of levels before a 64-bit hash is fully consumed and any further collision falls back to
+/// the leaf bucket.
+const MAX_LEVEL: u32 = u64::BITS.div_ceil(BITS);
+
+fn chunk(hash: u64, level: u32) -> usize {
+    ((hash >> (level.min(MAX_LEVEL) * BITS)) & MASK) as usize
+}
+
+/// A node in the trie. Branches are kept compact: only occupied slots are materialized, tracked by
+/// `bitmap`, so an empty trie costs nothing and a sparse one pays only for the slots it uses.
+enum Node<K, V> {
+    Empty,
+    Leaf(u64, Rc<Vec<(K, V)>>),
+    Branch(u32, Rc<Vec<Rc<Node<K, V>>>>),
+}
+
+/// Position of `slot` within a branch's compacted children, i.e. the number of occupied slots
+/// before it.
+fn branch_index(bitmap: u32, slot: usize) -> usize {
+    (bitmap & ((1 << slot) - 1)).count_ones() as usize
+}
+
+impl<K, V> Clone for Node<K, V> {
+    fn clone(&self) -> Self {
+        match self {
+            Node::Empty => Node::Empty,
+            Node::Leaf(hash, bucket) => Node::Leaf(*hash, Rc::clone(bucket)),
+            Node::Branch(bitmap, children) => Node::Branch(*bitmap, Rc::clone(children)),
+        }
+    }
+}
+
+/// Builds the sub-trie holding both `(h1, bucket1)` and the fresh `(hash2, key2, value2)`, which
+/// collided at `level`. Recurses one level at a time until the two hashes diverge or the hash is
+/// fully consumed, in which case they land together in one collision bucket.
+fn merge_leaves<K, V>(
+    h1: u64,
+    bucket1: Rc<Vec<(K, V)>>,
+    hash2: u64,
+    key2: K,
+    value2: V,
+    level: u32,
+) -> Rc<Node<K, V>>
+where
+    K: Clone,
+    V: Clone,
+{
+    if level >= MAX_LEVEL {
+        let mut bucket = (*bucket1).clone();
+        bucket.push((key2, value2));
+        return Rc::new(Node::Leaf(h1, Rc::new(bucket)));
+    }
+
+    let idx1 = chunk(h1, level);
+    let idx2 = chunk(hash2, level);
+
+    if idx1 == idx2 {
+        let child = merge_leaves(h1, bucket1, hash2, key2, value2, level + 1);
+        return Rc::new(Node::Branch(1 << idx1, Rc::new(vec![child])));
+    }
+
+    let leaf1 = Rc::new(Node::Leaf(h1, bucket1));
+    let leaf2 = Rc::new(Node::Leaf(hash2, Rc::new(vec![(key2, value2)])));
+    let bitmap = (1 << idx1) | (1 << idx2);
+    let children = if idx1 < idx2 {
+        vec![leaf1, leaf2]
+    } else {
+        vec![leaf2, leaf1]
+    };
+    Rc::new(Node::Branch(bitmap, Rc::new(children)))
+}
+
+fn get<'a, K, V, Q>(node: &'a Node<K, V>, key: &Q, hash: u64, level: u32) -> Option<&'a V>
+where
+    K: Borrow<Q>,
+    Q: ?Sized + Eq,
+{
+    match node {
+        Node::Empty => None,
+        Node::Leaf(h, bucket) if *h == hash => {
+            bucket.iter().find(|(k, _)| k.borrow() == key).map(|(_, v)| v)
+        }
+        Node::Leaf(_, _) => None,
+        Node::Branch(bitmap, children) => {
+            let slot = chunk(hash, level);
+            let bit = 1 << slot;
+            if bitmap & bit == 0 {
+                return None;
+            }
+            get(&children[branch_index(*bitmap, slot)], key, hash, level + 1)
+        }
+    }
+}
+
+/// Inserts `(key, value)` into the sub-trie rooted at `node`, returning the new root and the value
+/// previously stored under `key`, if any. Only the nodes on the path from `node` to the touched
+/// leaf are copied; every other node is reused via its [`Rc`].
+fn insert<K, V>(node: &Rc<Node<K, V>>, key: K, value: V, hash: u64, level: u32) -> (Rc<Node<K, V>>, Option<V>)
+where
+    K: Clone + Eq,
+    V: Clone,
+{
+    match &**node {
+        Node::Empty => (Rc::new(Node::Leaf(hash, Rc::new(vec![(key, value)]))), None),
+        Node::Leaf(h, bucket) if *h == hash => {
+            if let Some(pos) = bucket.iter().position(|(k, _)| *k == key) {
+                let mut new_bucket = (**bucket).clone();
+                let old = std::mem::replace(&mut new_bucket[pos].1, value);
+                (Rc::new(Node::Leaf(hash, Rc::new(new_bucket))), Some(old))
+            } else {
+                let mut new_bucket = (**bucket).clone();
+                new_bucket.push((key, value));
+                (Rc::new(Node::Leaf(hash, Rc::new(new_bucket))), None)
+            }
+        }
+        Node::Leaf(h, bucket) => (
+            merge_leaves(*h, Rc::clone(bucket), hash, key, value, level),
+            None,
+        ),
+        Node::Branch(bitmap, children) => {
+            let slot = chunk(hash, level);
+            let bit = 1 << slot;
+            let pos = branch_index(*bitmap, slot);
+
+            if bitmap & bit == 0 {
+                let mut new_children = (**children).clone();
+                new_children.insert(pos, Rc::new(Node::Leaf(hash, Rc::new(vec![(key, value)]))));
+                (Rc::new(Node::Branch(bitmap | bit, Rc::new(new_children))), None)
+            } else {
+                let (new_child, old) = insert(&children[pos], key, value, hash, level + 1);
+                let mut new_children = (**children).clone();
+                new_children[pos] = new_child;
+                (Rc::new(Node::Branch(*bitmap, Rc::new(new_children))), old)
+            }
+        }
+    }
+}
+
+/// Removes `key` from the sub-trie rooted at `node`, returning the new root and the removed value,
+/// if any.
+fn remove<K, V, Q>(node: &Rc<Node<K, V>>, key: &Q, hash: u64, level: u32) -> (Rc<Node<K, V>>, Option<V>)
+where
+    K: Clone + Borrow<Q>,
+    V: Clone,
+    Q: ?Sized + Eq,
+{
+    match &**node {
+        Node::Empty => (Rc::clone(node), None),
+        Node::Leaf(h, bucket) if *h == hash => match bucket.iter().position(|(k, _)| k.borrow() == key) {
+            None => (Rc::clone(node), None),
+            Some(pos) => {
+                let mut new_bucket = (**bucket).clone();
+                let (_, old) = new_bucket.remove(pos);
+                let new_node = if new_bucket.is_empty() {
+                    Rc::new(Node::Empty)
+                } else {
+                    Rc::new(Node::Leaf(hash, Rc::new(new_bucket)))
+                };
+                (new_node, Some(old))
+            }
+        },
+        Node::Leaf(_, _) => (Rc::clone(node), None),
+        Node::Branch(bitmap, children) => {
+            let slot = chunk(hash, level);
+            let bit = 1 << slot;
+            if bitmap & bit == 0 {
+                return (Rc::clone(node), None);
+            }
+
+            let pos = branch_index(*bitmap, slot);
+            let (new_child, old) = remove(&children[pos], key, hash, level + 1);
+            if old.is_none() {
+                return (Rc::clone(node), None);
+            }
+
+            let mut new_children = (**children).clone();
+            if matches!(&*new_child, Node::Empty) {
+                new_children.remove(pos);
+                let new_bitmap = bitmap & !bit;
+                let new_node = if new_bitmap == 0 {
+                    Rc::new(Node::Empty)
+                } else {
+                    Rc::new(Node::Branch(new_bitmap, Rc::new(new_children)))
+                };
+                (new_node, old)
+            } else {
+                new_children[pos] = new_child;
+                (Rc::new(Node::Branch(*bitmap, Rc::new(new_children))), old)
+            }
+        }
+    }
+}
+
+fn for_each<'a, K, V>(node: &'a Node<K, V>, f: &mut impl FnMut(&'a K, &'a V)) {
+    match node {
+        Node::Empty => {}
+        Node::Leaf(_, bucket) => bucket.iter().for_each(|(k, v)| f(k, v)),
+        Node::Branch(_, children) => children.iter().for_each(|child| for_each(child, f)),
+    }
+}
+
+/// A persistent hash array mapped trie.
+///
+/// Interior nodes are [`Rc`]-shared, so `clone` only bumps a reference count instead of
+/// deep-copying the whole structure, and `insert`/`remove` only copy the spine of nodes from the
+/// root down to the touched leaf rather than the whole trie — the rest is shared with whoever else
+/// still holds it. This is what makes the per-element [`Decompose::split`] affordable on the
+/// [`GSetHamt`]/[`AWSetHamt`] replicas that back it, unlike [`GSet`]/[`AWSet`] which deep-copy their
+/// `HashSet`/`HashMap` on every `clone`.
+///
+/// [`Decompose::split`]: super::Decompose::split
+/// [`GSetHamt`]: super::GSetHamt
+/// [`AWSetHamt`]: super::AWSetHamt
+/// [`GSet`]: super::GSet
+/// [`AWSet`]: super::AWSet
+pub(crate) struct Hamt<K, V> {
+    root: Rc<Node<K, V>>,
+    len: usize,
+    hasher: RandomState,
+}
+
+impl<K, V> Hamt<K, V> {
+    #[must_use]
+    pub(crate) fn new() -> Self {
+        Self {
+            root: Rc::new(Node::Empty),
+            len: 0,
+            hasher: RandomState::new(),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Visits every entry in arbitrary order.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        let mut entries = Vec::with_capacity(self.len);
+        for_each(&self.root, &mut |k, v| entries.push((k, v)));
+        entries.into_iter()
+    }
+}
+
+impl<K, V> Hamt<K, V>
+where
+    K: Clone + Eq + Hash,
+{
+    pub(crate) fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        get(&self.root, key, self.hasher.hash_one(key), 0)
+    }
+
+    pub(crate) fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.get(key).is_some()
+    }
+}
+
+impl<K, V> Hamt<K, V>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+{
+    /// Inserts `key` with `value`, returning the previous value if the key was already present.
+    pub(crate) fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let hash = self.hasher.hash_one(&key);
+        let (new_root, old) = insert(&self.root, key, value, hash, 0);
+        self.root = new_root;
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+
+    /// Removes `key`, returning its value if it was present.
+    pub(crate) fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        let hash = self.hasher.hash_one(key);
+        let (new_root, old) = remove(&self.root, key, hash, 0);
+        self.root = new_root;
+        if old.is_some() {
+            self.len -= 1;
+        }
+        old
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for Hamt<K, V>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut trie = Self::new();
+        trie.extend(iter);
+        trie
+    }
+}
+
+impl<K, V> Extend<(K, V)> for Hamt<K, V>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K, V> Clone for Hamt<K, V> {
+    /// Clones the trie in `O(1)`: the root `Rc` is shared until one side mutates, at which point
+    /// only the spine touched by that mutation is copied.
+    fn clone(&self) -> Self {
+        Self {
+            root: Rc::clone(&self.root),
+            len: self.len,
+            hasher: self.hasher.clone(),
+        }
+    }
+}
+
+impl<K, V> Default for Hamt<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> PartialEq for Hamt<K, V>
+where
+    K: Clone + Eq + Hash,
+    V: Clone + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self.iter().all(|(k, v)| other.get(k).is_some_and(|o| o == v))
+    }
+}
+
+impl<K, V> Eq for Hamt<K, V>
+where
+    K: Clone + Eq + Hash,
+    V: Clone + Eq,
+{
+}
+
+impl<K, V> Debug for Hamt<K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Hamt;
+
+    #[test]
+    fn insert_and_get() {
+        let mut trie: Hamt<u64, u64> = Hamt::new();
+        for i in 0..1_000 {
+            assert!(trie.insert(i, i * 2).is_none());
+        }
+
+        assert_eq!(trie.len(), 1_000);
+        for i in 0..1_000 {
+            assert_eq!(trie.get(&i), Some(&(i * 2)));
+        }
+        assert!(trie.get(&1_000).is_none());
+    }
+
+    #[test]
+    fn remove_shrinks_the_trie() {
+        let mut trie: Hamt<u64, u64> = Hamt::from_iter((0..256).map(|i| (i, i)));
+        for i in 0..128 {
+            assert_eq!(trie.remove(&i), Some(i));
+        }
+
+        assert_eq!(trie.len(), 128);
+        for i in 0..128 {
+            assert!(trie.get(&i).is_none());
+        }
+    }
+
+    #[test]
+    fn clone_is_independent_after_mutation() {
+        let mut original: Hamt<u64, u64> = Hamt::from_iter((0..64).map(|i| (i, i)));
+        let snapshot = original.clone();
+
+        original.insert(64, 64);
+        original.remove(&0);
+
+        assert_eq!(snapshot.len(), 64);
+        assert!(snapshot.get(&0).is_some());
+        assert!(snapshot.get(&64).is_none());
+    }
+
+    #[test]
+    fn equality_is_order_independent() {
+        let forward: Hamt<u64, u64> = Hamt::from_iter((0..200).map(|i| (i, i)));
+        let backward: Hamt<u64, u64> = Hamt::from_iter((0..200).rev().map(|i| (i, i)));
+        assert_eq!(forward, backward);
+    }
+}
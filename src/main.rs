@@ -7,8 +7,11 @@ use std::{
 };
 
 use crate::{
-    crdt::{AWSet, GSet, Measure},
-    sync::{baseline::Baseline, bloombuckets::BloomBuckets, buckets::Buckets, Algorithm},
+    crdt::{AWSet, GSet, LWWRegister, Measure, ORMap, PNCounter},
+    sync::{
+        baseline::Baseline, bloombuckets::BloomBuckets, buckets::Buckets, iblt::Iblt,
+        merkle::MerkleBuckets, Algorithm,
+    },
     tracker::{Bandwidth, DefaultEvent, DefaultTracker, Telemetry},
 };
 
@@ -16,13 +19,15 @@ use crdt::{Decompose, Extract};
 use rand::{
     distributions::{Alphanumeric, Bernoulli, DistString, Distribution, Uniform},
     rngs::StdRng,
-    SeedableRng,
+    Rng, SeedableRng,
 };
 
 mod bloom;
 mod crdt;
+mod report;
 mod sync;
 mod tracker;
+mod wire;
 
 fn gsets_with(len: usize, similar: f64, rng: &mut StdRng) -> (GSet<String>, GSet<String>) {
     assert!(
@@ -88,8 +93,8 @@ fn awsets_with(
         }
     }
 
-    let mut local = common.clone();
-    let mut remote = common;
+    let mut local = common.clone().fork(rng.gen());
+    let mut remote = common.fork(rng.gen());
 
     for _ in 0..diffs {
         let len = dist.sample(rng);
@@ -123,10 +128,141 @@ fn awsets_with(
     (local, remote)
 }
 
+/// Generates two `PNCounter` replicas, each tallying `len` independent contributions (e.g. one
+/// per device or session) under their own identity, so splitting a sync into per-contribution
+/// deltas is actually meaningful instead of collapsing into a single running total.
+fn pncounters_with(len: usize, similar: f64, rng: &mut StdRng) -> (PNCounter<u64>, PNCounter<u64>) {
+    assert!(
+        (0.0..=1.0).contains(&similar),
+        "similarity ratio should be in (0.0..=1.0)"
+    );
+
+    let sims = (len as f64 * similar) as usize;
+    let diffs = len - sims;
+    let ratio = Bernoulli::new(0.5).unwrap();
+
+    let (mut local, mut remote) = (PNCounter::new(), PNCounter::new());
+    let mut id = 0u64;
+
+    for _ in 0..sims {
+        id += 1;
+
+        if ratio.sample(rng) {
+            local.increment(&id);
+            remote.increment(&id);
+        } else {
+            local.decrement(&id);
+            remote.decrement(&id);
+        }
+    }
+
+    for _ in 0..diffs {
+        id += 1;
+        if ratio.sample(rng) {
+            local.increment(&id);
+        } else {
+            local.decrement(&id);
+        }
+
+        id += 1;
+        if ratio.sample(rng) {
+            remote.increment(&id);
+        } else {
+            remote.decrement(&id);
+        }
+    }
+
+    (local, remote)
+}
+
+/// Generates two `LWWRegister` replicas that start out forked from the same written value. With
+/// probability `similar` nothing else happens, so the replicas are already converged; otherwise
+/// both sides perform a concurrent, conflicting write under the same logical timestamp, leaving
+/// the dot tiebreak to pick a winner once they sync.
+fn lwwregisters_with(similar: f64, rng: &mut StdRng) -> (LWWRegister<String>, LWWRegister<String>) {
+    assert!(
+        (0.0..=1.0).contains(&similar),
+        "similarity ratio should be in (0.0..=1.0)"
+    );
+
+    let dist = Uniform::new_inclusive(5, 80);
+
+    let mut common = LWWRegister::new();
+    let len = dist.sample(rng);
+    common.set(1, Alphanumeric.sample_string(rng, len));
+
+    let mut local = common.clone().fork(rng.gen());
+    let mut remote = common.fork(rng.gen());
+
+    if !rng.gen_bool(similar) {
+        let len = dist.sample(rng);
+        local.set(2, Alphanumeric.sample_string(rng, len));
+
+        let len = dist.sample(rng);
+        remote.set(2, Alphanumeric.sample_string(rng, len));
+    }
+
+    (local, remote)
+}
+
+/// Generates two `ORMap` replicas whose values are themselves `GSet`s, following the same
+/// common-then-fork shape as [`awsets_with`] so the shared portion keeps identical dots across
+/// both replicas instead of diverging on every "shared" key.
+fn ormaps_with(
+    len: usize,
+    similar: f64,
+    rng: &mut StdRng,
+) -> (ORMap<String, GSet<String>>, ORMap<String, GSet<String>>) {
+    assert!(
+        (0.0..=1.0).contains(&similar),
+        "similarity ratio should be in (0.0..=1.0)"
+    );
+
+    let sims = (len as f64 * similar) as usize;
+    let diffs = len - sims;
+    let dist = Uniform::new_inclusive(5, 80);
+
+    let mut common = ORMap::new();
+
+    for _ in 0..sims {
+        let key = Alphanumeric.sample_string(rng, 8);
+        common.insert(key.clone(), GSet::new());
+
+        let len = dist.sample(rng);
+        let value = Alphanumeric.sample_string(rng, len);
+        common.update(&key, |gset| gset.insert(value));
+    }
+
+    let mut local = common.clone().fork(rng.gen());
+    let mut remote = common.fork(rng.gen());
+
+    for _ in 0..diffs {
+        let key = Alphanumeric.sample_string(rng, 8);
+        local.insert(key.clone(), GSet::new());
+        let len = dist.sample(rng);
+        local.update(&key, |gset| gset.insert(Alphanumeric.sample_string(rng, len)));
+
+        let key = Alphanumeric.sample_string(rng, 8);
+        remote.insert(key.clone(), GSet::new());
+        let len = dist.sample(rng);
+        remote.update(&key, |gset| gset.insert(Alphanumeric.sample_string(rng, len)));
+    }
+
+    assert_eq!(local.len(), len);
+    assert_eq!(remote.len(), len);
+    (local, remote)
+}
+
 type Replica<T> = (T, Bandwidth);
 
+/// Round-trip latency assumed for every link in the experiments, dominating the cost of
+/// algorithms that need several round trips to converge.
+const RTT: Duration = Duration::from_millis(50);
+/// Probability that any given round trip has to be retransmitted.
+const LOSS: f64 = 0.01;
+
 /// Runs the specified protocol and outputs the metrics obtained.
-fn run<T, A>(algo: &A, similar: f64, local: Replica<T>, remote: Replica<T>)
+fn run<T, A>(algo: &A, similar: f64, local: Replica<T>, remote: Replica<T>, seed: u64)
 where
     T: Clone + Decompose<Decomposition = T> + Default + Extract + Measure,
     A: Algorithm<T, Tracker = DefaultTracker> + Display,
@@ -139,7 +275,7 @@ where
     let (mut local, upload) = local;
     let (mut remote, download) = remote;
 
-    let mut tracker = DefaultTracker::new(download, upload);
+    let mut tracker = DefaultTracker::new(download, upload, RTT, LOSS, seed);
     algo.sync(&mut local, &mut remote, &mut tracker);
 
     let diffs = tracker.false_matches();
@@ -149,18 +285,15 @@ where
 
     let events = tracker.events();
     println!(
-        "{algo} {} {} {:.3}",
+        "{algo} {} {} {} {:.3}",
         events.iter().map(DefaultEvent::state).sum::<usize>(),
         events.iter().map(DefaultEvent::metadata).sum::<usize>(),
-        events
-            .iter()
-            .filter_map(|e| e.duration().ok())
-            .sum::<Duration>()
-            .as_secs_f64(),
+        tracker.rounds(),
+        tracker.duration().as_secs_f64(),
     );
 }
 
-fn run_with<T>(similar: f64, local: T, remote: T)
+fn run_with<T>(similar: f64, local: T, remote: T, rng: &mut StdRng)
 where
     T: Clone + Decompose<Decomposition = T> + Default + Extract + Measure,
 {
@@ -187,6 +320,7 @@ where
             similar,
             (local.clone(), upload),
             (remote.clone(), download),
+            rng.gen(),
         );
 
         for lf in [0.2, 1.0, 5.0] {
@@ -196,6 +330,7 @@ where
                 similar,
                 (local.clone(), upload),
                 (remote.clone(), download),
+                rng.gen(),
             );
         }
 
@@ -207,9 +342,32 @@ where
                     similar,
                     (local.clone(), upload),
                     (remote.clone(), download),
+                    rng.gen(),
                 );
             }
         }
+
+        for diff in [(1.0 - similar).max(0.01), 0.1] {
+            let algo = Iblt::new(diff);
+            run(
+                &algo,
+                similar,
+                (local.clone(), upload),
+                (remote.clone(), download),
+                rng.gen(),
+            );
+        }
+
+        for lf in [1.25, 5.0] {
+            let algo = MerkleBuckets::new(lf, 4);
+            run(
+                &algo,
+                similar,
+                (local.clone(), upload),
+                (remote.clone(), download),
+                rng.gen(),
+            );
+        }
     }
 }
 
@@ -248,7 +406,7 @@ fn main() {
                 exec_time.elapsed()
             );
 
-            run_with(s, local, remote);
+            run_with(s, local, remote, &mut rng);
         }),
 
         // NOTE: AWSets generated with 20% of elements removed. This value is pretty conservative for
@@ -263,7 +421,37 @@ fn main() {
                 exec_time.elapsed()
             );
 
-            run_with(s, local, remote);
+            run_with(s, local, remote, &mut rng);
+        }),
+
+        "pncounter" => similarities.for_each(|s| {
+            let (local, remote) = pncounters_with(20_000, s, &mut rng);
+            eprintln!(
+                "[{:.2?}] pncounters with similarity of {s} generated",
+                exec_time.elapsed()
+            );
+
+            run_with(s, local, remote, &mut rng);
+        }),
+
+        "lwwregister" => similarities.for_each(|s| {
+            let (local, remote) = lwwregisters_with(s, &mut rng);
+            eprintln!(
+                "[{:.2?}] lwwregisters with similarity of {s} generated",
+                exec_time.elapsed()
+            );
+
+            run_with(s, local, remote, &mut rng);
+        }),
+
+        "ormap" => similarities.for_each(|s| {
+            let (local, remote) = ormaps_with(20_000, s, &mut rng);
+            eprintln!(
+                "[{:.2?}] ormaps with similarity of {s} generated",
+                exec_time.elapsed()
+            );
+
+            run_with(s, local, remote, &mut rng);
         }),
         _ => unreachable!(),
     };
@@ -0,0 +1,141 @@
+use std::fmt::Write;
+
+use crate::tracker::{DefaultEvent, DefaultTracker, Telemetry};
+
+/// Renders a completed sync session, captured by a [`DefaultTracker`], into machine-readable
+/// artifacts so the communication cost of different algorithms can be diffed and visualized
+/// across parameter sweeps instead of read from raw debug output.
+pub struct Report<'a> {
+    tracker: &'a DefaultTracker,
+}
+
+impl<'a> Report<'a> {
+    #[inline]
+    #[must_use]
+    pub fn new(tracker: &'a DefaultTracker) -> Self {
+        Self { tracker }
+    }
+
+    /// Emits a Graphviz [DOT] diagram of the session. Each transfer round is a node annotated with
+    /// its direction, state and metadata bytes, and estimated duration; directed edges order the
+    /// rounds in the sequence they were registered.
+    ///
+    /// [DOT]: https://graphviz.org/doc/info/lang.html
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph session {\n    rankdir=LR;\n");
+
+        for (i, event) in self.tracker.events().iter().enumerate() {
+            writeln!(
+                dot,
+                "    r{i} [label=\"{} | state={}B metadata={}B | {:.3}s\"];",
+                direction(event),
+                event.state(),
+                event.metadata(),
+                seconds(event),
+            )
+            .expect("writing to a String is infallible");
+
+            if i > 0 {
+                writeln!(dot, "    r{} -> r{i};", i - 1).expect("writing to a String is infallible");
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Emits one delimiter-separated row per event (direction, state, metadata, total bytes,
+    /// estimated seconds) followed by a summary row that totals the transfer and includes the
+    /// session's final false-match count.
+    pub fn to_csv(&self, delimiter: char) -> String {
+        let mut csv = String::new();
+        writeln!(
+            csv,
+            "direction{delimiter}state{delimiter}metadata{delimiter}bytes{delimiter}seconds"
+        )
+        .expect("writing to a String is infallible");
+
+        let events = self.tracker.events();
+        for event in events {
+            writeln!(
+                csv,
+                "{}{delimiter}{}{delimiter}{}{delimiter}{}{delimiter}{:.3}",
+                direction(event),
+                event.state(),
+                event.metadata(),
+                event.bytes(),
+                seconds(event),
+            )
+            .expect("writing to a String is infallible");
+        }
+
+        let state = events.iter().map(DefaultEvent::state).sum::<usize>();
+        let metadata = events.iter().map(DefaultEvent::metadata).sum::<usize>();
+        let bytes = events.iter().map(DefaultEvent::bytes).sum::<usize>();
+        let seconds = events.iter().map(seconds).sum::<f64>();
+
+        writeln!(
+            csv,
+            "total (false_matches={}){delimiter}{state}{delimiter}{metadata}{delimiter}{bytes}{delimiter}{seconds:.3}",
+            self.tracker.false_matches(),
+        )
+        .expect("writing to a String is infallible");
+
+        csv
+    }
+}
+
+/// Returns the human-readable direction of an event.
+fn direction(event: &DefaultEvent) -> &'static str {
+    match event {
+        DefaultEvent::LocalToRemote { .. } => "local->remote",
+        DefaultEvent::RemoteToLocal { .. } => "remote->local",
+    }
+}
+
+/// Returns the estimated transfer time of an event in seconds, treating an unbounded link as free.
+fn seconds(event: &DefaultEvent) -> f64 {
+    event.duration().unwrap_or_default().as_secs_f64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tracker::Bandwidth;
+    use std::time::Duration;
+
+    fn session() -> DefaultTracker {
+        let mut tracker = DefaultTracker::new(Bandwidth::Kbps(0.5), Bandwidth::Kbps(0.5), Duration::from_millis(50), 0.0, 0);
+        tracker.register(DefaultEvent::LocalToRemote {
+            state: 0,
+            metadata: 16,
+            upload: tracker.upload(),
+        });
+        tracker.register(DefaultEvent::RemoteToLocal {
+            state: 30,
+            metadata: 8,
+            download: tracker.download(),
+        });
+        tracker.finish(0);
+        tracker
+    }
+
+    #[test]
+    fn test_dot_has_one_node_and_edge_per_round() {
+        let tracker = session();
+        let dot = Report::new(&tracker).to_dot();
+
+        assert_eq!(dot.matches("[label").count(), 2);
+        assert_eq!(dot.matches(" -> ").count(), 1);
+    }
+
+    #[test]
+    fn test_csv_has_header_rows_and_summary() {
+        let tracker = session();
+        let csv = Report::new(&tracker).to_csv(',');
+
+        // Header, one row per event and the summary row.
+        assert_eq!(csv.lines().count(), 4);
+        assert!(csv.contains("false_matches=0"));
+    }
+}
@@ -1,6 +1,7 @@
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     hash::{BuildHasher, RandomState},
+    marker::PhantomData,
     mem,
 };
 
@@ -14,6 +15,11 @@ pub mod baseline;
 pub mod bloom;
 pub mod bloombuckets;
 pub mod buckets;
+pub mod chunked;
+pub mod encrypted;
+pub mod iblt;
+pub mod merkle;
+pub mod net;
 
 pub trait Algorithm<T> {
     type Tracker: Telemetry;
@@ -21,6 +27,45 @@ pub trait Algorithm<T> {
     fn sync(&self, local: &mut T, remote: &mut T, tracker: &mut Self::Tracker);
 }
 
+/// A page of buckets produced by [`Dispatcher::dispatch_batched`]: the same `len`-bucket layout
+/// [`Dispatcher::dispatch`] builds in one shot, except each page only holds up to `batch_size`
+/// decompositions, so the caller never has to keep the full bucket set resident at once.
+pub struct BatchedDispatch<'a, I, T, H> {
+    deltas: I,
+    len: usize,
+    hasher: &'a H,
+    batch_size: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<I, T, H> Iterator for BatchedDispatch<'_, I, T, H>
+where
+    I: Iterator<Item = T>,
+    T: Clone + Extract,
+    H: BuildHasher,
+{
+    type Item = Vec<BTreeMap<u64, T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut buckets = vec![BTreeMap::new(); self.len];
+        let mut filled = 0;
+
+        for d in self.deltas.by_ref() {
+            let hash = self.hasher.hash_one(d.extract());
+            let idx = usize::try_from(hash).unwrap() % self.len;
+
+            buckets[idx].insert(hash, d);
+            filled += 1;
+
+            if filled >= self.batch_size {
+                return Some(buckets);
+            }
+        }
+
+        (filled > 0).then_some(buckets)
+    }
+}
+
 pub trait Dispatcher<T>
 where
     T: Clone + Decompose<Decomposition = T> + Extract,
@@ -43,12 +88,68 @@ where
         buckets
     }
 
+    /// Paged counterpart to [`Dispatcher::dispatch`] that yields the bucket set as several pages
+    /// instead of a single `Vec`, each page holding at most `batch_size` decompositions.
+    ///
+    /// Replicas with millions of delta-groups make [`Dispatcher::dispatch`]'s all-at-once
+    /// materialization the dominant memory cost of a sync round. Flushing a page once `batch_size`
+    /// entries have filled it bounds the bucket maps built at once to a configurable ceiling,
+    /// trading it for more round-trips over the page boundary — the same
+    /// batch-over-bound-memory trade thin-provisioning-tools' block walker makes via its
+    /// `get_batch_size`. The decompositions themselves are not streamed: `split()` returns a
+    /// `Vec`, so every decomposition of `replica` is materialized when this is called.
+    fn dispatch_batched<'a, H: BuildHasher>(
+        &self,
+        replica: &T,
+        len: usize,
+        hasher: &'a H,
+        batch_size: usize,
+    ) -> BatchedDispatch<'a, std::vec::IntoIter<T>, T, H> {
+        BatchedDispatch {
+            deltas: replica.split().into_iter(),
+            len,
+            hasher,
+            batch_size,
+            _marker: PhantomData,
+        }
+    }
+
     fn hashes<H: BuildHasher>(buckets: &[BTreeMap<u64, T>], hasher: &H) -> Vec<u64> {
         buckets
             .iter()
             .map(|b| hasher.hash_one(b.keys().fold(String::new(), |acc, h| format!("{acc}{h}"))))
             .collect()
     }
+
+    /// Streaming counterpart to [`Dispatcher::hashes`] that accumulates each bucket's keys page by
+    /// page as `pages` is consumed, so the digest comparison never needs the whole bucket set —
+    /// only `len` running key accumulators — resident at once.
+    ///
+    /// The accumulators are [`BTreeSet`]s rather than partially-folded strings: [`Dispatcher::hashes`]
+    /// hashes a bucket's keys in the globally sorted order a single `BTreeMap` iterates in, and a
+    /// page only ever holds a subset of a bucket's keys, so folding per page as it arrives would
+    /// hash a merely page-locally sorted order instead — two replicas whose identical bucket
+    /// contents happen to split into different pages would then digest to different hashes. Folding
+    /// once, after every page has contributed its keys to the set, recovers the same globally
+    /// sorted order `hashes` relies on.
+    fn hashes_batched<H: BuildHasher>(
+        pages: impl Iterator<Item = Vec<BTreeMap<u64, T>>>,
+        len: usize,
+        hasher: &H,
+    ) -> Vec<u64> {
+        let mut accum = vec![BTreeSet::new(); len];
+
+        for page in pages {
+            for (acc, bucket) in accum.iter_mut().zip(page) {
+                acc.extend(bucket.into_keys());
+            }
+        }
+
+        accum
+            .iter()
+            .map(|keys| hasher.hash_one(keys.iter().fold(String::new(), |acc, h| format!("{acc}{h}"))))
+            .collect()
+    }
 }
 
 pub trait BuildFilter<T>
@@ -80,3 +181,38 @@ where
             + mem::size_of::<u64>()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crdt::GSet;
+
+    struct TestDispatcher;
+
+    impl<T> Dispatcher<T> for TestDispatcher where T: Clone + Decompose<Decomposition = T> + Extract {}
+
+    #[test]
+    fn hashes_batched_matches_hashes_regardless_of_page_boundaries() {
+        let mut gset = GSet::new();
+        let items = "Stuck In A Moment You Can't Get Out Of"
+            .split_whitespace()
+            .collect::<Vec<_>>();
+
+        for item in items {
+            gset.insert(item.to_string());
+        }
+
+        let hasher = RandomState::new();
+        let len = 4;
+
+        let buckets = TestDispatcher.dispatch(&gset, len, &hasher);
+        let expected = TestDispatcher::hashes(&buckets, &hasher);
+
+        // A batch size that splits the decompositions across several pages must still fold each
+        // bucket's keys in the same globally sorted order `hashes` does.
+        let pages = TestDispatcher.dispatch_batched(&gset, len, &hasher, 2);
+        let batched = TestDispatcher::hashes_batched(pages, len, &hasher);
+
+        assert_eq!(batched, expected);
+    }
+}
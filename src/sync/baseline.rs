@@ -1,11 +1,13 @@
-use std::{fmt::Display, marker::PhantomData};
+use std::{fmt::Display, io, marker::PhantomData};
+
+use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
     crdt::{Decompose, Measure},
     tracker::{DefaultEvent, DefaultTracker, Telemetry},
 };
 
-use super::Algorithm;
+use super::{net::Protocol, Algorithm};
 
 #[derive(Clone, Copy, Debug, Default)]
 pub struct Baseline<T> {
@@ -68,8 +70,117 @@ where
     }
 }
 
+/// Initiator half of [`Baseline`]'s protocol, driven over a real [`Transport`](super::net::Transport)
+/// instead of mutating a peer's replica in-process. Ships the local replica's full state, then
+/// joins back whatever the responder reports missing.
+pub struct Initiator<T> {
+    local: T,
+    sent: bool,
+    done: bool,
+}
+
+impl<T> Initiator<T> {
+    #[inline]
+    #[must_use]
+    pub fn new(local: T) -> Self {
+        Self {
+            local,
+            sent: false,
+            done: false,
+        }
+    }
+
+    /// Consumes the initiator, returning the synchronized replica.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.local
+    }
+}
+
+impl<T> Protocol for Initiator<T>
+where
+    T: Decompose<Decomposition = T> + Serialize + DeserializeOwned,
+{
+    fn step(&mut self, incoming: &[u8]) -> io::Result<Option<Vec<u8>>> {
+        if !self.sent {
+            self.sent = true;
+
+            let bytes = bincode::serialize(&self.local)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            return Ok(Some(bytes));
+        }
+
+        let local_unseen: T = bincode::deserialize(incoming)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        self.local.join(vec![local_unseen]);
+        self.done = true;
+
+        Ok(None)
+    }
+
+    fn is_done(&self) -> bool {
+        self.done
+    }
+}
+
+/// Responder half of [`Baseline`]'s protocol. Waits for the initiator's full state, joins in
+/// whatever it was missing, and reports back what the initiator itself is missing.
+pub struct Responder<T> {
+    remote: T,
+    started: bool,
+    done: bool,
+}
+
+impl<T> Responder<T> {
+    #[inline]
+    #[must_use]
+    pub fn new(remote: T) -> Self {
+        Self {
+            remote,
+            started: false,
+            done: false,
+        }
+    }
+
+    /// Consumes the responder, returning the synchronized replica.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.remote
+    }
+}
+
+impl<T> Protocol for Responder<T>
+where
+    T: Decompose<Decomposition = T> + Serialize + DeserializeOwned,
+{
+    fn step(&mut self, incoming: &[u8]) -> io::Result<Option<Vec<u8>>> {
+        if !self.started {
+            self.started = true;
+            return Ok(None);
+        }
+
+        let local_state: T = bincode::deserialize(incoming)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let remote_unseen = local_state.difference(&self.remote);
+        let local_unseen = self.remote.difference(&local_state);
+        self.remote.join(vec![remote_unseen]);
+        self.done = true;
+
+        let bytes = bincode::serialize(&local_unseen)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(Some(bytes))
+    }
+
+    fn is_done(&self) -> bool {
+        self.done
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use super::*;
     use crate::{crdt::GSet, tracker::Bandwidth};
 
@@ -102,7 +213,7 @@ mod tests {
         };
 
         let (download, upload) = (Bandwidth::Kbps(0.5), Bandwidth::Kbps(0.5));
-        let mut tracker = DefaultTracker::new(download, upload);
+        let mut tracker = DefaultTracker::new(download, upload, Duration::from_millis(50), 0.0, 0);
 
         let baseline = Baseline::new();
         baseline.sync(&mut local, &mut remote, &mut tracker);
@@ -111,4 +222,72 @@ mod tests {
         assert_eq!(bytes, vec![30, 35]);
         assert_eq!(tracker.false_matches(), 0);
     }
+
+    #[test]
+    fn test_initiator_responder_over_channel_transport() {
+        use crate::{sync::net::Driver, wire::ChannelTransport};
+
+        let local = {
+            let mut gset = GSet::<String>::new();
+            let items = "Stuck In A Moment You Can't Get Out Of"
+                .split_whitespace()
+                .collect::<Vec<_>>();
+
+            for item in items {
+                gset.insert(item.to_string());
+            }
+
+            gset
+        };
+
+        let remote = {
+            let mut gset = GSet::<String>::new();
+            let items = "I Still Haven't Found What I'm Looking For"
+                .split_whitespace()
+                .collect::<Vec<_>>();
+
+            for item in items {
+                gset.insert(item.to_string());
+            }
+
+            gset
+        };
+
+        let expected_len = local.len() + remote.len();
+        let (download, upload) = (Bandwidth::Kbps(0.5), Bandwidth::Kbps(0.5));
+        let (local_transport, remote_transport) = ChannelTransport::pair();
+
+        let initiator = std::thread::spawn(move || {
+            let mut tracker = DefaultTracker::new(download, upload, Duration::from_millis(50), 0.0, 0);
+            let mut driver = Driver::new(local_transport, Initiator::new(local), &mut tracker);
+            driver.run().unwrap();
+
+            let local = driver.into_protocol().into_inner();
+            let bytes = tracker.events().iter().map(DefaultEvent::bytes).sum::<usize>();
+
+            (local, bytes)
+        });
+
+        let responder = std::thread::spawn(move || {
+            let mut tracker = DefaultTracker::new(download, upload, Duration::from_millis(50), 0.0, 0);
+            let mut driver = Driver::new(remote_transport, Responder::new(remote), &mut tracker);
+            driver.run().unwrap();
+
+            let remote = driver.into_protocol().into_inner();
+            let bytes = tracker.events().iter().map(DefaultEvent::bytes).sum::<usize>();
+
+            (remote, bytes)
+        });
+
+        let (local, local_bytes) = initiator.join().unwrap();
+        let (remote, remote_bytes) = responder.join().unwrap();
+
+        assert_eq!(local, remote);
+        assert_eq!(local.len(), expected_len);
+
+        // Both sides should be charged for what was actually serialized on the wire, not
+        // `mem::size_of_val`.
+        assert!(local_bytes > 0);
+        assert!(remote_bytes > 0);
+    }
 }
@@ -199,6 +199,7 @@ where
 mod tests {
     use super::*;
     use crate::{crdt::GSet, tracker::Bandwidth};
+    use std::time::Duration;
 
     #[test]
     fn test_sync() {
@@ -229,7 +230,7 @@ mod tests {
         };
 
         let (download, upload) = (Bandwidth::Kbps(0.5), Bandwidth::Kbps(0.5));
-        let mut tracker = DefaultTracker::new(download, upload);
+        let mut tracker = DefaultTracker::new(download, upload, Duration::from_millis(50), 0.0, 0);
         let bloom_buckets = BloomBuckets::new(0.01, 1.0);
 
         bloom_buckets.sync(&mut local, &mut remote, &mut tracker);
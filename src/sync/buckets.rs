@@ -139,7 +139,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use std::mem;
+    use std::{mem, time::Duration};
 
     use super::*;
     use crate::{crdt::GSet, tracker::Bandwidth};
@@ -173,7 +173,7 @@ mod tests {
         };
 
         let (download, upload) = (Bandwidth::Kbps(0.5), Bandwidth::Kbps(0.5));
-        let mut tracker = DefaultTracker::new(download, upload);
+        let mut tracker = DefaultTracker::new(download, upload, Duration::from_millis(50), 0.0, 0);
         let buckets = Buckets::new(1.25);
 
         buckets.sync(&mut local, &mut remote, &mut tracker);
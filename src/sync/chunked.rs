@@ -0,0 +1,243 @@
+use std::{
+    fmt::Display,
+    hash::{BuildHasher, RandomState},
+    marker::PhantomData,
+};
+
+use crate::{
+    crdt::{Decompose, Extract, Measure},
+    tracker::{DefaultEvent, DefaultTracker, Telemetry},
+};
+
+use super::Algorithm;
+
+/// Content-defined chunking reconciliation.
+///
+/// Instead of summarizing the join-decompositions with a probabilistic membership filter (as
+/// [`Bloom`] does), this algorithm groups them into variable-sized, content-defined chunks and
+/// exchanges one strong fingerprint per chunk. Only the chunks whose fingerprint the peer is
+/// missing are transferred, which gives a deterministic (no false positives) reconciliation path
+/// — valuable when false matches are unacceptable.
+///
+/// The chunk boundaries are derived from a rolling gear hash over the canonically sorted
+/// decomposition hashes. Because the boundaries depend only on content and not on alignment, a
+/// single differing decomposition perturbs only the chunk that carries it rather than shifting
+/// every downstream boundary.
+///
+/// [`Bloom`]: super::bloom::Bloom
+#[derive(Clone, Copy, Debug)]
+pub struct Chunked<T> {
+    mask: u64,
+    min: usize,
+    max: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Chunked<T> {
+    /// Creates a [`Chunked`] algorithm whose boundaries fall, on average, every `avg`
+    /// decompositions. The chunk size is clamped to the `[avg / 4, avg * 4]` interval so that no
+    /// chunk is pathologically small or large.
+    #[inline]
+    #[must_use]
+    pub fn new(avg: usize) -> Self {
+        assert!(avg > 0, "average chunk size should be greater than 0");
+
+        // A boundary is declared whenever the low `log2(avg)` bits of the rolling hash are zero,
+        // which happens on average once every `avg` decompositions.
+        let bits = (usize::BITS - avg.leading_zeros()).saturating_sub(1);
+
+        Self {
+            mask: (1u64 << bits) - 1,
+            min: (avg / 4).max(1),
+            max: avg * 4,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for Chunked<T> {
+    fn default() -> Self {
+        Self::new(8)
+    }
+}
+
+impl<T> Display for Chunked<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Chunked[min={},max={}]", self.min, self.max)
+    }
+}
+
+/// Mixes a single byte into the gear-hash table. The constant is the 64-bit golden ratio, which
+/// spreads each input byte across the whole word and keeps the table identical on both peers.
+#[inline]
+fn gear(byte: u8) -> u64 {
+    u64::from(byte)
+        .wrapping_add(1)
+        .wrapping_mul(0x9E37_79B9_7F4A_7C15)
+}
+
+impl<T> Chunked<T>
+where
+    T: Clone + Decompose<Decomposition = T> + Extract,
+{
+    /// Groups the join-decompositions of `replica` into content-defined chunks and pairs each
+    /// chunk with a strong fingerprint. Both the ordering of the decompositions and the chunk
+    /// boundaries are deterministic, so peers seeded with the same `hasher` agree on the grouping.
+    fn chunks(&self, replica: &T, hasher: &RandomState) -> Vec<(u64, Vec<T>)> {
+        // 1. Canonically sort the decompositions by their extracted hash.
+        let mut decompositions = replica
+            .split()
+            .into_iter()
+            .map(|d| (hasher.hash_one(d.extract()), d))
+            .collect::<Vec<_>>();
+        decompositions.sort_unstable_by_key(|(hash, _)| *hash);
+
+        // 2. Roll a gear hash over the stream of hashes and cut a boundary whenever the low bits
+        //    vanish, clamped by the configured minimum and maximum chunk sizes.
+        let mut chunks = vec![];
+        let (mut roll, mut start) = (0u64, 0);
+
+        for (i, (hash, _)) in decompositions.iter().enumerate() {
+            for byte in hash.to_le_bytes() {
+                roll = (roll << 1).wrapping_add(gear(byte));
+            }
+
+            let len = i - start + 1;
+            if len >= self.min && (roll & self.mask == 0 || len >= self.max) {
+                chunks.push((start, i + 1));
+                (roll, start) = (0, i + 1);
+            }
+        }
+
+        if start < decompositions.len() {
+            chunks.push((start, decompositions.len()));
+        }
+
+        // 3. Fingerprint each chunk over the hashes it carries and detach the decompositions.
+        chunks
+            .into_iter()
+            .map(|(start, end)| {
+                let slice = &decompositions[start..end];
+                let fingerprint =
+                    hasher.hash_one(slice.iter().fold(String::new(), |acc, (h, _)| {
+                        format!("{acc}{h}")
+                    }));
+
+                (
+                    fingerprint,
+                    slice.iter().map(|(_, d)| d.clone()).collect::<Vec<_>>(),
+                )
+            })
+            .collect()
+    }
+}
+
+impl<T> Algorithm<T> for Chunked<T>
+where
+    T: Clone + Decompose<Decomposition = T> + Default + Extract + Measure,
+{
+    type Tracker = DefaultTracker;
+
+    fn sync(&self, local: &mut T, remote: &mut T, tracker: &mut Self::Tracker) {
+        assert!(
+            tracker.is_ready(),
+            "tracker should be ready, i.e., no captured events and not finished"
+        );
+
+        // NOTE: The boundary policy must be deterministic across both peers.
+        let hasher = RandomState::new();
+
+        // 1. Chunk the local decompositions and send the ordered list of fingerprints to the
+        //    remote replica. Only the fingerprint metadata travels in this round.
+        let local_chunks = self.chunks(local, &hasher);
+
+        tracker.register(DefaultEvent::LocalToRemote {
+            state: 0,
+            metadata: local_chunks.len() * std::mem::size_of::<u64>(),
+            upload: tracker.upload(),
+        });
+
+        // 2. Chunk the remote decompositions and ship back the chunks whose fingerprint the local
+        //    replica did not announce, alongside the remote fingerprint list.
+        let remote_chunks = self.chunks(remote, &hasher);
+        let local_fingerprints = local_chunks.iter().map(|(f, _)| *f).collect::<Vec<_>>();
+
+        let local_unknown = remote_chunks
+            .iter()
+            .filter(|(f, _)| !local_fingerprints.contains(f))
+            .flat_map(|(_, chunk)| chunk.iter().cloned())
+            .collect::<Vec<_>>();
+
+        tracker.register(DefaultEvent::RemoteToLocal {
+            state: local_unknown.iter().map(<T as Measure>::size_of).sum(),
+            metadata: remote_chunks.len() * std::mem::size_of::<u64>(),
+            download: tracker.download(),
+        });
+
+        // 3. Symmetrically, send the local chunks that the remote replica is missing.
+        let remote_fingerprints = remote_chunks.iter().map(|(f, _)| *f).collect::<Vec<_>>();
+        let remote_unknown = local_chunks
+            .iter()
+            .filter(|(f, _)| !remote_fingerprints.contains(f))
+            .flat_map(|(_, chunk)| chunk.iter().cloned())
+            .collect::<Vec<_>>();
+
+        tracker.register(DefaultEvent::LocalToRemote {
+            state: remote_unknown.iter().map(<T as Measure>::size_of).sum(),
+            metadata: 0,
+            upload: tracker.upload(),
+        });
+
+        // 4. Join the missing chunks on both replicas.
+        local.join(local_unknown);
+        remote.join(remote_unknown);
+
+        // 5. Sanity check. Content-defined chunking is deterministic, so both replicas fully sync.
+        tracker.finish(<T as Measure>::false_matches(local, remote));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{crdt::GSet, tracker::Bandwidth};
+    use std::time::Duration;
+
+    #[test]
+    fn test_sync() {
+        let mut local = {
+            let mut gset = GSet::new();
+            let items = "Stuck In A Moment You Can't Get Out Of"
+                .split_whitespace()
+                .collect::<Vec<_>>();
+
+            for item in items {
+                gset.insert(item.to_string());
+            }
+
+            gset
+        };
+
+        let mut remote = {
+            let mut gset = GSet::new();
+            let items = "I Still Haven't Found What I'm Looking For"
+                .split_whitespace()
+                .collect::<Vec<_>>();
+
+            for item in items {
+                gset.insert(item.to_string());
+            }
+
+            gset
+        };
+
+        let (download, upload) = (Bandwidth::Kbps(0.5), Bandwidth::Kbps(0.5));
+        let mut tracker = DefaultTracker::new(download, upload, Duration::from_millis(50), 0.0, 0);
+
+        let chunked = Chunked::new(4);
+        chunked.sync(&mut local, &mut remote, &mut tracker);
+
+        assert_eq!(tracker.false_matches(), 0);
+        assert_eq!(local, remote);
+    }
+}
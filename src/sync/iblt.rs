@@ -0,0 +1,318 @@
+use std::{
+    cmp::max,
+    collections::HashMap,
+    fmt::Display,
+    hash::{BuildHasher, Hash, RandomState},
+    marker::PhantomData,
+    mem,
+};
+
+use crate::{
+    crdt::{Decompose, Extract, Measure},
+    tracker::{DefaultEvent, DefaultTracker, Telemetry},
+};
+
+use super::Algorithm;
+
+/// Number of cells each key is folded into, reusing the double-hashing scheme from
+/// [`crate::bloom::BloomFilter`] to pick the positions.
+const HASHES: u64 = 4;
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Cell {
+    count: i64,
+    key_sum: u64,
+    hash_sum: u64,
+}
+
+impl Cell {
+    fn is_empty(&self) -> bool {
+        self.count == 0 && self.key_sum == 0 && self.hash_sum == 0
+    }
+
+    fn is_pure(&self) -> bool {
+        self.count == 1 || self.count == -1
+    }
+}
+
+/// An [Invertible Bloom Lookup Table](https://doi.org/10.1007/978-3-642-24850-6_7) over a set of
+/// keys, which lets a peer *decode* the symmetric difference against another peer's table instead
+/// of having to ship whole buckets as [`super::buckets::Buckets`] does.
+struct Table {
+    cells: Vec<Cell>,
+    hashers: [RandomState; 2],
+    checksum: RandomState,
+}
+
+impl Table {
+    fn new(cells: usize, hashers: [RandomState; 2], checksum: RandomState) -> Self {
+        Self {
+            cells: vec![Cell::default(); max(cells, 1)],
+            hashers,
+            checksum,
+        }
+    }
+
+    /// Inserts `value`, deriving its key from the first hasher, and returns that key so the
+    /// caller can map it back to the originating join-decomposition.
+    fn insert<V: Hash>(&mut self, value: &V) -> u64 {
+        let key = self.hashers[0].hash_one(value);
+        self.toggle(key, 1);
+
+        key
+    }
+
+    /// Applies (or inverts, for `sign == -1`) the insert operation for `key` across all `HASHES`
+    /// of its cells.
+    // NOTE: Positions are derived from the key itself, not the original value, so that decoding
+    // can recompute them from a recovered key alone.
+    fn toggle(&mut self, key: u64, sign: i64) {
+        let h1 = self.hashers[1].hash_one(key);
+        let checksum = self.checksum.hash_one(key);
+        let len = self.cells.len();
+
+        (0..HASHES).for_each(|i| {
+            let pos = usize::try_from(key.wrapping_add(i.wrapping_mul(h1))).unwrap() % len;
+            let cell = &mut self.cells[pos];
+
+            cell.count += sign;
+            cell.key_sum ^= key;
+            cell.hash_sum ^= checksum;
+        });
+    }
+
+    /// Computes the cell-wise difference against `other`, leaving only the cells that summarize
+    /// the symmetric difference between the two tables.
+    fn difference(mut self, other: &Self) -> Self {
+        self.cells
+            .iter_mut()
+            .zip(other.cells.iter())
+            .for_each(|(cell, o)| {
+                cell.count -= o.count;
+                cell.key_sum ^= o.key_sum;
+                cell.hash_sum ^= o.hash_sum;
+            });
+
+        self
+    }
+
+    /// Repeatedly peels pure cells until none remain, recovering each differing key along with
+    /// the sign of the cell it was peeled from (`1` if only the table being subtracted from had
+    /// it, `-1` if only the table subtracted had it). Returns `None` if cells remain that cannot
+    /// be decoded, in which case the caller should fall back to a full state exchange.
+    fn decode(mut self) -> Option<Vec<(u64, i64)>> {
+        let mut recovered = Vec::new();
+
+        while let Some(idx) = self.cells.iter().position(|cell| {
+            cell.is_pure() && cell.hash_sum == self.checksum.hash_one(cell.key_sum)
+        }) {
+            let cell = self.cells[idx];
+            let sign = cell.count.signum();
+
+            recovered.push((cell.key_sum, sign));
+            self.toggle(cell.key_sum, -sign);
+        }
+
+        self.cells.iter().all(Cell::is_empty).then_some(recovered)
+    }
+
+    fn size_of(&self) -> usize {
+        self.cells.len() * mem::size_of::<Cell>() + mem::size_of::<RandomState>() * 3
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Iblt<T> {
+    diff: f64,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Iblt<T> {
+    #[inline]
+    #[must_use]
+    pub fn new(diff: f64) -> Self {
+        assert!(
+            diff > 0.0,
+            "expected difference ratio should be greater than 0.0"
+        );
+
+        Self {
+            diff,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for Iblt<T> {
+    fn default() -> Self {
+        Self {
+            diff: 0.1,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Display for Iblt<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "IBLT[diff={}]", self.diff)
+    }
+}
+
+impl<T> Iblt<T>
+where
+    T: Clone + Decompose<Decomposition = T> + Extract,
+{
+    fn table_from(
+        replica: &T,
+        cells: usize,
+        hashers: [RandomState; 2],
+        checksum: RandomState,
+    ) -> (Table, HashMap<u64, T>) {
+        let mut table = Table::new(cells, hashers, checksum);
+        let index = replica
+            .split()
+            .into_iter()
+            .map(|d| (table.insert(&d.extract()), d))
+            .collect();
+
+        (table, index)
+    }
+}
+
+impl<T> Algorithm<T> for Iblt<T>
+where
+    T: Clone + Decompose<Decomposition = T> + Default + Extract + Measure,
+{
+    type Tracker = DefaultTracker;
+
+    fn sync(&self, local: &mut T, remote: &mut T, tracker: &mut Self::Tracker) {
+        assert!(
+            tracker.is_ready(),
+            "tracker should be ready, i.e., no captured events and not finished"
+        );
+
+        let hashers = [RandomState::new(), RandomState::new()];
+        let checksum = RandomState::new();
+        let cells = (1.5 * self.diff * <T as Measure>::len(local) as f64).ceil() as usize;
+
+        // 1. Build an IBLT over the local join-decompositions, keeping an index from key to
+        //    decomposition so that any recovered key can be mapped back, then send the table to
+        //    the remote replica.
+        // NOTE: The hash functions must be seeded identically on both peers, exactly like the
+        // deterministic bucketing note in `Buckets::sync`.
+        let (local_table, local_index) =
+            Self::table_from(local, cells, hashers.clone(), checksum.clone());
+
+        tracker.register(DefaultEvent::LocalToRemote {
+            state: 0,
+            metadata: local_table.size_of(),
+            upload: tracker.upload(),
+        });
+
+        // 2. Repeat on the remote replica and subtract the local table cell-wise, leaving only
+        //    the cells that summarize the symmetric difference between the replicas.
+        let (remote_table, remote_index) = Self::table_from(remote, cells, hashers, checksum);
+        let diff = remote_table.difference(&local_table);
+
+        // 3. Decode the symmetric difference by peeling pure cells. A `+1` cell means the key is
+        //    only known to the remote replica, a `-1` cell means it is only known to the local
+        //    replica. Fall back to a full state exchange if the table could not be fully peeled.
+        let Some(recovered) = diff.decode() else {
+            let local_state = local.clone();
+
+            tracker.register(DefaultEvent::LocalToRemote {
+                state: <T as Measure>::size_of(&local_state),
+                metadata: 0,
+                upload: tracker.upload(),
+            });
+
+            let remote_unseen = local_state.difference(remote);
+            let local_unseen = remote.difference(&local_state);
+
+            tracker.register(DefaultEvent::RemoteToLocal {
+                state: <T as Measure>::size_of(&local_unseen),
+                metadata: 0,
+                download: tracker.download(),
+            });
+
+            remote.join(vec![remote_unseen]);
+            local.join(vec![local_unseen]);
+
+            tracker.finish(<T as Measure>::false_matches(local, remote));
+            return;
+        };
+
+        let (remote_only, local_only): (Vec<_>, Vec<_>) =
+            recovered.into_iter().partition(|(_, sign)| *sign > 0);
+
+        let local_unknown = remote_only
+            .into_iter()
+            .filter_map(|(key, _)| remote_index.get(&key).cloned())
+            .collect::<Vec<_>>();
+        let remote_unknown = local_only
+            .into_iter()
+            .filter_map(|(key, _)| local_index.get(&key).cloned())
+            .collect::<Vec<_>>();
+
+        // 4. Ship the recovered join-decompositions that each replica is missing.
+        tracker.register(DefaultEvent::RemoteToLocal {
+            state: local_unknown.iter().map(<T as Measure>::size_of).sum(),
+            metadata: 0,
+            download: tracker.download(),
+        });
+        tracker.register(DefaultEvent::LocalToRemote {
+            state: remote_unknown.iter().map(<T as Measure>::size_of).sum(),
+            metadata: 0,
+            upload: tracker.upload(),
+        });
+
+        local.join(local_unknown);
+        remote.join(remote_unknown);
+
+        // 5. Sanity check.
+        tracker.finish(<T as Measure>::false_matches(local, remote));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{crdt::GSet, tracker::Bandwidth};
+    use std::time::Duration;
+
+    #[test]
+    fn test_sync() {
+        let mut local = {
+            let mut gset = GSet::new();
+            let items = "Stuck In A Moment You Can't Get Out Of"
+                .split_whitespace()
+                .collect::<Vec<_>>();
+
+            for item in items {
+                gset.insert(item.to_string());
+            }
+
+            gset
+        };
+
+        let mut remote = {
+            let mut gset = GSet::new();
+            let items = "I Still Haven't Found What I'm Looking For"
+                .split_whitespace()
+                .collect::<Vec<_>>();
+
+            for item in items {
+                gset.insert(item.to_string());
+            }
+
+            gset
+        };
+
+        let (download, upload) = (Bandwidth::Kbps(0.5), Bandwidth::Kbps(0.5));
+        let mut tracker = DefaultTracker::new(download, upload, Duration::from_millis(50), 0.0, 0);
+        let iblt = Iblt::new(1.0);
+
+        iblt.sync(&mut local, &mut remote, &mut tracker);
+        assert_eq!(tracker.false_matches(), 0);
+    }
+}
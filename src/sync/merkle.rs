@@ -0,0 +1,186 @@
+use std::{fmt::Display, hash::RandomState, iter::zip, marker::PhantomData, mem};
+
+use crate::{
+    crdt::{Decompose, Extract, Measure},
+    tracker::{DefaultEvent, DefaultTracker, Telemetry},
+};
+
+use super::{Algorithm, Dispatcher};
+
+/// When neither side of a mismatching bucket holds more than this many decompositions, the bucket
+/// is shipped outright instead of being split into another round of sub-buckets.
+const LEAF: usize = 2;
+
+#[derive(Clone, Copy, Debug)]
+pub struct MerkleBuckets<T> {
+    lf: f64,
+    depth: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T> MerkleBuckets<T> {
+    #[inline]
+    #[must_use]
+    pub fn new(lf: f64, depth: usize) -> Self {
+        assert!(lf > 0.0, "load factor should be greater than 0.0");
+        assert!(depth > 0, "depth should be greater than 0");
+
+        Self {
+            lf,
+            depth,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for MerkleBuckets<T> {
+    fn default() -> Self {
+        Self {
+            lf: 1.0,
+            depth: 4,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Display for MerkleBuckets<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Merkle[lf={},depth={}]", self.lf, self.depth)
+    }
+}
+
+impl<T> Dispatcher<T> for MerkleBuckets<T> where T: Clone + Decompose<Decomposition = T> + Extract {}
+
+impl<T> MerkleBuckets<T>
+where
+    T: Clone + Decompose<Decomposition = T> + Default + Extract + Measure,
+{
+    /// Reconciles a single pair of mismatching buckets. On a mismatch, re-hashes the bucket's
+    /// contents into a fresh round of sub-buckets with a fresh salt and recurses into whichever
+    /// sub-buckets still disagree, until `depth` is exhausted or a bucket is small enough to ship
+    /// outright, returning the decompositions each side is missing.
+    fn reconcile(&self, local: T, remote: T, depth: usize, tracker: &mut DefaultTracker) -> (T, T) {
+        let len = <T as Measure>::len(&local).max(<T as Measure>::len(&remote));
+
+        if depth == 0 || len <= LEAF {
+            let local_unknown = remote.difference(&local);
+            let remote_unknown = local.difference(&remote);
+
+            tracker.register(DefaultEvent::LocalToRemote {
+                state: <T as Measure>::size_of(&remote_unknown),
+                metadata: 0,
+                upload: tracker.upload(),
+            });
+            tracker.register(DefaultEvent::RemoteToLocal {
+                state: <T as Measure>::size_of(&local_unknown),
+                metadata: 0,
+                download: tracker.download(),
+            });
+
+            return (local_unknown, remote_unknown);
+        }
+
+        // NOTE: The salt must be fresh per level but still deterministic across both peers, same
+        // as the top-level dispatch in `Buckets::sync`.
+        let hasher = RandomState::new();
+        let sub_buckets = usize::max(2, (self.lf * len as f64) as usize);
+
+        let local_buckets = self.dispatch(&local, sub_buckets, &hasher);
+        let local_hashes = Self::hashes(&local_buckets, &hasher);
+
+        let remote_buckets = self.dispatch(&remote, sub_buckets, &hasher);
+        let remote_hashes = Self::hashes(&remote_buckets, &hasher);
+
+        // Only the hashes are exchanged at this level; a mismatch recurses one level deeper
+        // instead of shipping the whole bucket.
+        tracker.register(DefaultEvent::LocalToRemote {
+            state: 0,
+            metadata: mem::size_of_val(local_hashes.as_slice()),
+            upload: tracker.upload(),
+        });
+
+        let (mut local_unknown, mut remote_unknown) = (T::default(), T::default());
+
+        zip(local_buckets, remote_buckets)
+            .zip(zip(local_hashes, remote_hashes))
+            .filter(|(_, (local_hash, remote_hash))| local_hash != remote_hash)
+            .for_each(|((local_bucket, remote_bucket), _)| {
+                let mut local_state = T::default();
+                local_state.join(local_bucket.into_values().collect());
+
+                let mut remote_state = T::default();
+                remote_state.join(remote_bucket.into_values().collect());
+
+                let (l, r) = self.reconcile(local_state, remote_state, depth - 1, tracker);
+                local_unknown.join(vec![l]);
+                remote_unknown.join(vec![r]);
+            });
+
+        (local_unknown, remote_unknown)
+    }
+}
+
+impl<T> Algorithm<T> for MerkleBuckets<T>
+where
+    T: Clone + Decompose<Decomposition = T> + Default + Extract + Measure,
+{
+    type Tracker = DefaultTracker;
+
+    fn sync(&self, local: &mut T, remote: &mut T, tracker: &mut Self::Tracker) {
+        assert!(
+            tracker.is_ready(),
+            "tracker should be ready, i.e., no captured events and not finished"
+        );
+
+        let (local_unknown, remote_unknown) =
+            self.reconcile(local.clone(), remote.clone(), self.depth, tracker);
+
+        local.join(vec![local_unknown]);
+        remote.join(vec![remote_unknown]);
+
+        tracker.finish(<T as Measure>::false_matches(local, remote));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{crdt::GSet, tracker::Bandwidth};
+    use std::time::Duration;
+
+    #[test]
+    fn test_sync() {
+        let mut local = {
+            let mut gset = GSet::new();
+            let items = "Stuck In A Moment You Can't Get Out Of"
+                .split_whitespace()
+                .collect::<Vec<_>>();
+
+            for item in items {
+                gset.insert(item.to_string());
+            }
+
+            gset
+        };
+
+        let mut remote = {
+            let mut gset = GSet::new();
+            let items = "I Still Haven't Found What I'm Looking For"
+                .split_whitespace()
+                .collect::<Vec<_>>();
+
+            for item in items {
+                gset.insert(item.to_string());
+            }
+
+            gset
+        };
+
+        let (download, upload) = (Bandwidth::Kbps(0.5), Bandwidth::Kbps(0.5));
+        let mut tracker = DefaultTracker::new(download, upload, Duration::from_millis(50), 0.0, 0);
+        let merkle = MerkleBuckets::new(1.25, 4);
+
+        merkle.sync(&mut local, &mut remote, &mut tracker);
+        assert_eq!(tracker.false_matches(), 0);
+    }
+}
@@ -0,0 +1,184 @@
+use std::{
+    io::{self, Read, Write},
+    net::TcpStream,
+    os::fd::{AsRawFd, RawFd},
+};
+
+use crate::tracker::{DefaultEvent, DefaultTracker, Telemetry};
+
+/// A framed, bidirectional channel over which the synchronization phases exchange their messages.
+///
+/// Each phase of an [`Algorithm`] produces a self-contained message (a filter, a partition, a set
+/// of bucket hashes); a [`Transport`] is responsible for moving those messages across a connection
+/// without the algorithm having to know whether the peer is in the same process or on another
+/// host.
+///
+/// [`Algorithm`]: super::Algorithm
+pub trait Transport {
+    /// Sends a single framed message, returning the number of bytes written on the wire.
+    fn send(&mut self, msg: &[u8]) -> io::Result<usize>;
+
+    /// Receives the next framed message, returning its payload.
+    fn recv(&mut self) -> io::Result<Vec<u8>>;
+}
+
+/// A [`Transport`] backed by a concrete socket.
+///
+/// The underlying socket is exposed through [`Connection::as_raw_fd`] so callers can register the
+/// connection with a `poll`/`epoll`-style reactor and multiplex it with timers and other I/O
+/// rather than blocking a thread on every exchange.
+#[derive(Debug)]
+pub struct Connection<S = TcpStream> {
+    socket: S,
+}
+
+impl<S> Connection<S> {
+    #[inline]
+    #[must_use]
+    pub fn new(socket: S) -> Self {
+        Self { socket }
+    }
+
+    /// Returns a shared reference to the underlying socket.
+    #[inline]
+    pub fn socket(&self) -> &S {
+        &self.socket
+    }
+
+    /// Consumes the connection and returns the underlying socket.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.socket
+    }
+}
+
+/// A [`Transport`] over a real TCP socket.
+pub type TcpTransport = Connection<TcpStream>;
+
+impl<S> AsRawFd for Connection<S>
+where
+    S: AsRawFd,
+{
+    fn as_raw_fd(&self) -> RawFd {
+        self.socket.as_raw_fd()
+    }
+}
+
+impl<S> Transport for Connection<S>
+where
+    S: Read + Write,
+{
+    fn send(&mut self, msg: &[u8]) -> io::Result<usize> {
+        // Length-prefixed framing so the peer can recover message boundaries off a byte stream.
+        let len = u64::try_from(msg.len())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        self.socket.write_all(&len.to_be_bytes())?;
+        self.socket.write_all(msg)?;
+
+        Ok(msg.len() + std::mem::size_of::<u64>())
+    }
+
+    fn recv(&mut self) -> io::Result<Vec<u8>> {
+        let mut len = [0u8; std::mem::size_of::<u64>()];
+        self.socket.read_exact(&mut len)?;
+
+        let len = usize::try_from(u64::from_be_bytes(len))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let mut msg = vec![0u8; len];
+        self.socket.read_exact(&mut msg)?;
+
+        Ok(msg)
+    }
+}
+
+/// The phases a networked [`Algorithm`] steps through, expressed as message transformations so the
+/// [`Driver`] can advance the protocol as bytes arrive instead of blocking on a single call.
+///
+/// Each call consumes the message received from the peer (the first step receives an empty slice)
+/// and returns either the next message to send or `None` when it has nothing to send at this step;
+/// [`Protocol::is_done`] reports when the local replica is synchronized.
+///
+/// [`Algorithm`]: super::Algorithm
+pub trait Protocol {
+    /// Advances the protocol by one message, mutating the local replica in place.
+    fn step(&mut self, incoming: &[u8]) -> io::Result<Option<Vec<u8>>>;
+
+    /// Returns `true` once the local replica has received everything it needs from the peer.
+    fn is_done(&self) -> bool;
+}
+
+/// Drives a [`Protocol`] over a [`Transport`], feeding real wire-byte counts into the tracker.
+///
+/// A caller embeds the driver in its own event loop: whenever the connection's file descriptor is
+/// reported readable, it calls [`Driver::advance`]; the driver reads one message, steps the
+/// protocol and writes the reply, recording the bytes actually moved so bandwidth estimates
+/// reflect the wire rather than `size_of_val`.
+pub struct Driver<'a, Tr, P> {
+    transport: Tr,
+    protocol: P,
+    tracker: &'a mut DefaultTracker,
+    sent_first: bool,
+}
+
+impl<'a, Tr, P> Driver<'a, Tr, P>
+where
+    Tr: Transport,
+    P: Protocol,
+{
+    #[inline]
+    #[must_use]
+    pub fn new(transport: Tr, protocol: P, tracker: &'a mut DefaultTracker) -> Self {
+        Self {
+            transport,
+            protocol,
+            tracker,
+            sent_first: false,
+        }
+    }
+
+    /// Steps the protocol once: receives a message (except for the opening send), advances the
+    /// local state and ships the reply, recording the transferred bytes. Returns `true` while the
+    /// protocol still expects further progress.
+    pub fn advance(&mut self) -> io::Result<bool> {
+        let incoming = if self.sent_first {
+            let msg = self.transport.recv()?;
+            self.tracker.register(DefaultEvent::RemoteToLocal {
+                state: msg.len(),
+                metadata: 0,
+                download: self.tracker.download(),
+            });
+
+            msg
+        } else {
+            self.sent_first = true;
+            vec![]
+        };
+
+        if let Some(outgoing) = self.protocol.step(&incoming)? {
+            let bytes = self.transport.send(&outgoing)?;
+            self.tracker.register(DefaultEvent::LocalToRemote {
+                state: bytes,
+                metadata: 0,
+                upload: self.tracker.upload(),
+            });
+        }
+
+        Ok(!self.protocol.is_done())
+    }
+
+    /// Runs the protocol to completion by repeatedly advancing it. Blocking convenience for
+    /// callers that do not need to multiplex the connection.
+    pub fn run(&mut self) -> io::Result<()> {
+        while self.advance()? {}
+        Ok(())
+    }
+
+    /// Consumes the driver, returning the protocol it was driving, e.g. to recover the
+    /// synchronized replica once [`Driver::run`] completes.
+    #[inline]
+    pub fn into_protocol(self) -> P {
+        self.protocol
+    }
+}
@@ -1,5 +1,7 @@
 use std::time::Duration;
 
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
 pub trait Telemetry {
     type Event;
 
@@ -104,23 +106,46 @@ impl DefaultEvent {
 }
 
 /// Default [`Tracker`] for operations over the Network.
+///
+/// Besides the bandwidth-bound cost of the exchanged payloads, a sync also pays for every round
+/// trip the link has to make: the `rtt` charges one latency per direction switch between
+/// [`DefaultEvent::LocalToRemote`] and [`DefaultEvent::RemoteToLocal`] registrations, and `loss`
+/// is the probability that any given round has to be retransmitted, each retransmission costing
+/// one extra `rtt`. Retransmissions are drawn from a `seed`-derived RNG so runs are reproducible.
 #[derive(Debug)]
 pub struct DefaultTracker {
     events: Vec<DefaultEvent>,
     diffs: Option<usize>,
     download: Bandwidth,
     upload: Bandwidth,
+    rtt: Duration,
+    loss: f64,
+    rng: StdRng,
+    rounds: usize,
+    last_direction: Option<bool>,
+    retransmissions: usize,
 }
 
 impl DefaultTracker {
     #[inline]
     #[must_use]
-    pub fn new(download: Bandwidth, upload: Bandwidth) -> Self {
+    pub fn new(download: Bandwidth, upload: Bandwidth, rtt: Duration, loss: f64, seed: u64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&loss),
+            "loss should be a probability in [0.0, 1.0]"
+        );
+
         Self {
             events: vec![],
             diffs: None,
             download,
             upload,
+            rtt,
+            loss,
+            rng: StdRng::seed_from_u64(seed),
+            rounds: 0,
+            last_direction: None,
+            retransmissions: 0,
         }
     }
 }
@@ -135,6 +160,49 @@ impl DefaultTracker {
     pub const fn upload(&self) -> Bandwidth {
         self.upload
     }
+
+    #[inline]
+    pub const fn rtt(&self) -> Duration {
+        self.rtt
+    }
+
+    #[inline]
+    pub const fn loss(&self) -> f64 {
+        self.loss
+    }
+
+    /// Number of round trips the session has made so far: one per direction switch between
+    /// [`DefaultEvent::LocalToRemote`] and [`DefaultEvent::RemoteToLocal`] registrations.
+    /// Consecutive events in the same direction are pipelined into the round already in flight.
+    #[inline]
+    pub const fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    /// Draws a seed from this tracker's RNG, e.g. to seed a private tracker wrapped by a
+    /// decorator [`Algorithm`](super::sync::Algorithm) while keeping the whole run reproducible
+    /// from a single top-level seed.
+    #[inline]
+    pub fn fork_seed(&mut self) -> u64 {
+        self.rng.gen()
+    }
+
+    /// Total duration of the session: the bandwidth-bound transfer time of every registered
+    /// event, plus one `rtt` per round trip and per retransmission of a round lost along the way.
+    #[inline]
+    pub fn duration(&self) -> Duration {
+        let transfer = self
+            .events
+            .iter()
+            .filter_map(|e| e.duration().ok())
+            .sum::<Duration>();
+
+        let latency = self.rtt
+            * u32::try_from(self.rounds + self.retransmissions)
+                .expect("round counts should comfortably fit in a u32");
+
+        transfer + latency
+    }
 }
 
 impl Telemetry for DefaultTracker {
@@ -146,6 +214,12 @@ impl Telemetry for DefaultTracker {
 
     fn register(&mut self, event: Self::Event) {
         if self.diffs.is_none() {
+            let direction = matches!(event, DefaultEvent::LocalToRemote { .. });
+            if self.last_direction.is_some_and(|last| last != direction) {
+                self.rounds += 1;
+            }
+            self.last_direction = Some(direction);
+
             self.events.push(event);
         }
     }
@@ -156,6 +230,10 @@ impl Telemetry for DefaultTracker {
 
     fn finish(&mut self, diffs: usize) {
         if self.diffs.is_none() {
+            self.retransmissions = (0..self.rounds)
+                .filter(|_| self.rng.gen_bool(self.loss))
+                .count();
+
             self.diffs = Some(diffs)
         }
     }